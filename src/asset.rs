@@ -54,22 +54,62 @@ pub trait Manager {
     fn image(&mut self, texture: Texture, pos: Position) -> Result<Image<Self::Texture>>;
     fn sprite(&mut self, animaiton: Animation, pos: Position) -> Result<Sprite<Self::Texture>>;
     fn font(&mut self, font: Font, size: u16) -> Result<Rc<Self::Font>>;
+
+    /// Like `texture` but loaded on its own and drawn with the given alpha,
+    /// leaving everything else drawing the same image untouched.
+    fn translucent_texture(&mut self, texture: Texture, alpha: u8) -> Result<Self::Texture>;
+    /// Like `sheet` but loaded on its own and drawn with the given alpha.
+    fn translucent_sheet(
+        &mut self,
+        animation: Animation,
+        alpha: u8,
+    ) -> Result<TileSheet<Self::Texture>>;
 }
 
-#[derive(Clone, Copy)]
+/// Textures that can be drawn see-through.
+pub trait Alpha {
+    fn set_alpha(&mut self, alpha: u8);
+}
+
+impl<'t> Alpha for sdl2::render::Texture<'t> {
+    fn set_alpha(&mut self, alpha: u8) {
+        self.set_blend_mode(sdl2::render::BlendMode::Blend);
+        self.set_alpha_mod(alpha);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Texture {
     Husky,
     Duck,
     Heart,
+    Spike,
+    Goal,
+    Ground(Ground),
+    //background and layer within it
+    Background(usize, usize),
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ground {
+    Center,
+    Left,
+    Right,
+    Top,
+    TopLeft,
+    TopRight,
+    SingleCenter,
+    SingleTop,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Animation {
     Husky,
     Duck,
     Coin,
     Gem,
     IdleCat,
+    WalkingCat,
 }
 
 #[derive(Clone, Copy)]
@@ -113,6 +153,34 @@ impl data::Game {
                 texture: &self.heart.texture.0,
                 dims: self.heart.out_size,
             },
+            Texture::Spike => TextureData {
+                texture: &self.spike.texture.0,
+                dims: self.spike.out_size,
+            },
+            Texture::Goal => TextureData {
+                texture: &self.goal.texture.0,
+                dims: self.goal.out_size,
+            },
+            Texture::Background(background, layer) => {
+                let layer = &self.backgrounds[background].layers[layer];
+                TextureData {
+                    texture: &layer.texture.0,
+                    dims: layer.out_size,
+                }
+            }
+            Texture::Ground(ground) => TextureData {
+                texture: match ground {
+                    Ground::Center => &self.ground.center.0,
+                    Ground::Left => &self.ground.left.0,
+                    Ground::Right => &self.ground.right.0,
+                    Ground::Top => &self.ground.top.0,
+                    Ground::TopLeft => &self.ground.top_left.0,
+                    Ground::TopRight => &self.ground.top_right.0,
+                    Ground::SingleCenter => &self.ground.single_center.0,
+                    Ground::SingleTop => &self.ground.single_top.0,
+                },
+                dims: self.ground.out_size,
+            },
         }
     }
 
@@ -123,6 +191,7 @@ impl data::Game {
             Animation::Coin => (self.coin.out_size, &self.coin.animation),
             Animation::Gem => (self.gem.out_size, &self.gem.animation),
             Animation::IdleCat => (self.cat.out_size, &self.cat.idle),
+            Animation::WalkingCat => (self.cat.out_size, &self.cat.walking),
         };
         AnimationData {
             dims,
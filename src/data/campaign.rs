@@ -0,0 +1,26 @@
+use crate::Result;
+
+use std::fs::File;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Stage {
+    pub name: String,
+    pub path: String,
+}
+
+/// Ordered list of the levels played one after another in a new game.
+#[derive(Debug, serde::Deserialize)]
+pub struct Campaign {
+    pub stages: Vec<Stage>,
+}
+
+impl Campaign {
+    pub fn load(path: &'static str) -> Result<Self> {
+        let f = File::open(path)?;
+        let campaign: Campaign = serde_yaml::from_reader(&f)?;
+        if campaign.stages.is_empty() {
+            return Err(failure::format_err!("{} has no stages", path));
+        }
+        Ok(campaign)
+    }
+}
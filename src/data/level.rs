@@ -1,7 +1,7 @@
 use super::Dimension;
 use crate::Result;
 
-use std::fs::File;
+use std::{fs::File, path::Path};
 
 #[derive(Debug, serde::Deserialize, Clone, Copy)]
 pub enum GroundKind {
@@ -41,16 +41,42 @@ pub struct Spike {
 
 #[derive(Debug, serde::Deserialize)]
 pub struct Level {
+    #[serde(default)]
+    pub name: Option<String>,
+    //seconds to reach the goal
+    #[serde(default = "Level::default_time_limit")]
+    pub time_limit: u64,
+    #[serde(default)]
+    pub par_score: Option<u32>,
+    //name of one of the backgrounds in the game data
+    #[serde(default)]
+    pub background: Option<String>,
+    //file of the track to play during the level
+    #[serde(default)]
+    pub music: Option<String>,
+    //tile the players start on
+    #[serde(default = "Level::default_spawn")]
+    pub spawn: Dimension,
     pub obstacles: Vec<Obstacle>,
     pub goal: Dimension,
     pub gems: Vec<Dimension>,
     pub coins: Vec<Dimension>,
     pub cats: Vec<Cat>,
     pub spikes: Vec<Spike>,
+    #[serde(default)]
+    pub checkpoints: Vec<Dimension>,
 }
 
 impl Level {
-    pub fn load(path: &'static str) -> Result<Self> {
+    fn default_time_limit() -> u64 {
+        100
+    }
+
+    fn default_spawn() -> Dimension {
+        Dimension { x: 5, y: 1 }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let f = File::open(path)?;
         serde_yaml::from_reader(&f).map_err(Into::into)
     }
@@ -1,9 +1,14 @@
+mod campaign;
 mod level;
 
-pub use self::level::{CatKind, GroundKind, Level, Obstacle};
+pub use self::{
+    campaign::{Campaign, Stage},
+    level::{CatKind, GroundKind, Level, Obstacle, Spike},
+};
 
 use crate::Result;
 
+use failure::format_err;
 use moho::{
     animation::animator,
     renderer::{Destination, Position},
@@ -57,6 +62,20 @@ pub struct Image {
     pub out_size: Dimension,
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct Layer {
+    pub texture: Texture,
+    pub out_size: Dimension,
+    pub parallax: f64,
+}
+
+/// Layers making up one of the backgrounds a level can pick.
+#[derive(Debug, serde::Deserialize)]
+pub struct Background {
+    pub name: String,
+    pub layers: Vec<Layer>,
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct Collectable {
     pub animation: Animation,
@@ -72,6 +91,8 @@ pub struct Ground {
     pub top: Texture,
     pub top_left: Texture,
     pub top_right: Texture,
+    pub single_center: Texture,
+    pub single_top: Texture,
     pub out_size: Dimension,
 }
 
@@ -83,7 +104,7 @@ pub struct Game {
     pub gem: Collectable,
     pub coin: Collectable,
     pub cat: Cat,
-    pub background: Image,
+    pub backgrounds: Vec<Background>,
     pub goal: Image,
     pub heart: Image,
     pub spike: Image,
@@ -92,7 +113,35 @@ pub struct Game {
 impl Game {
     pub fn load(path: &'static str) -> Result<Game> {
         let f = File::open(path)?;
-        serde_yaml::from_reader(&f).map_err(Into::into)
+        let game: Game = serde_yaml::from_reader(&f)?;
+        //layers are tiled, which takes a size to step by
+        let empty = game.backgrounds.iter().find(|b| {
+            b.layers
+                .iter()
+                .any(|l| l.out_size.x == 0 || l.out_size.y == 0)
+        });
+        if let Some(background) = empty {
+            return Err(format_err!(
+                "background {:?} has a layer with a zero out_size",
+                background.name
+            ));
+        }
+        Ok(game)
+    }
+
+    /// Index of the background with the given name; `None` picks the first one.
+    pub fn background(&self, name: Option<&str>) -> Result<usize> {
+        if self.backgrounds.is_empty() {
+            return Err(format_err!("no backgrounds are configured"));
+        }
+        match name {
+            Some(name) => self
+                .backgrounds
+                .iter()
+                .position(|b| b.name == name)
+                .ok_or_else(|| format_err!("unknown background {:?}", name)),
+            None => Ok(0),
+        }
     }
 
     pub fn animators(&self) -> Animators {
@@ -0,0 +1,98 @@
+use crate::{asset, data, game::camera::Camera, Result};
+
+use moho::{
+    renderer::{align, options, Draw, Renderer, Show},
+    texture::Texture,
+};
+
+struct Layer {
+    dims: glm::UVec2,
+    parallax: f64,
+}
+
+/// Background images drawn behind the level, each scrolling at a fraction of the camera speed.
+pub struct Background {
+    index: usize,
+    layers: Vec<Layer>,
+}
+
+impl Background {
+    pub fn new(index: usize, game: &data::Game) -> Self {
+        let layers = game.backgrounds[index]
+            .layers
+            .iter()
+            .map(|l| Layer {
+                dims: l.out_size.into(),
+                parallax: l.parallax,
+            })
+            .collect();
+        Background { index, layers }
+    }
+}
+
+struct LayerAssets<T> {
+    texture: T,
+    dims: glm::UVec2,
+    //how far into the first repeated image the view starts
+    offset: glm::IVec2,
+}
+
+pub struct Assets<T> {
+    layers: Vec<LayerAssets<T>>,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        background: &Background,
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let layers = background
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                asset_manager
+                    .texture(asset::Texture::Background(background.index, i))
+                    .map(|texture| LayerAssets {
+                        texture,
+                        dims: l.dims,
+                        offset: glm::ivec2(0, 0),
+                    })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Assets { layers }.next(background, camera))
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(mut self, background: &Background, camera: &Camera) -> Self {
+        let camera = camera.bottom_left();
+        for (assets, layer) in self.layers.iter_mut().zip(&background.layers) {
+            let scrolled = glm::dvec2(camera.x * layer.parallax, camera.y * layer.parallax);
+            assets.offset = glm::ivec2(
+                scrolled.x.round() as i32 % layer.dims.x as i32,
+                scrolled.y.round() as i32 % layer.dims.y as i32,
+            );
+        }
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        for layer in &self.layers {
+            let mut bottom = 720 + layer.offset.y;
+            while bottom > 0 {
+                let mut left = -layer.offset.x;
+                while left < 1280 {
+                    let dst = align::left(left).bottom(bottom).dims(layer.dims);
+                    renderer.draw(&layer.texture, options::at(dst))?;
+                    left += layer.dims.x as i32;
+                }
+                bottom -= layer.dims.y as i32;
+            }
+        }
+        Ok(())
+    }
+}
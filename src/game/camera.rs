@@ -0,0 +1,100 @@
+use crate::game::collision::Rectangle;
+
+use moho::renderer::{align, Destination};
+
+const VIEW: glm::DVec2 = glm::DVec2 { x: 1280., y: 720. };
+//how far the target can stray from the center of the view before the camera moves
+const DEAD_ZONE: glm::DVec2 = glm::DVec2 { x: 120., y: 90. };
+
+#[derive(Clone, Copy)]
+pub struct Camera {
+    bottom_left: glm::DVec2,
+    bounds: glm::DVec2,
+}
+
+impl Camera {
+    pub fn new(target: glm::DVec2, bounds: glm::UVec2) -> Self {
+        let mut camera = Camera {
+            bottom_left: target - VIEW / 2.,
+            bounds: glm::dvec2(bounds.x.into(), bounds.y.into()),
+        };
+        camera.clamp();
+        camera
+    }
+
+    pub fn update(&mut self, target: glm::DVec2) {
+        let center = self.bottom_left + VIEW / 2.;
+        let distance = target - center;
+        let follow = |distance: f64, dead_zone: f64| {
+            if distance > dead_zone {
+                distance - dead_zone
+            } else if distance < -dead_zone {
+                distance + dead_zone
+            } else {
+                0.
+            }
+        };
+        self.bottom_left.x += follow(distance.x, DEAD_ZONE.x);
+        self.bottom_left.y += follow(distance.y, DEAD_ZONE.y);
+        self.clamp();
+    }
+
+    /// World position of the bottom left corner of the view.
+    pub fn bottom_left(&self) -> glm::DVec2 {
+        self.bottom_left
+    }
+
+    /// Moves the view the least amount needed to show all of `area`, keeping
+    /// its center in view when it is too big to fit.
+    pub fn include(&mut self, area: &Rectangle) {
+        let fit = |bottom_left: f64, view: f64, min: f64, max: f64| {
+            if max - min > view {
+                (min + max - view) / 2.
+            } else {
+                bottom_left.min(min).max(max - view)
+            }
+        };
+        let top_right = area.top_right();
+        self.bottom_left.x = fit(self.bottom_left.x, VIEW.x, area.bottom_left.x, top_right.x);
+        self.bottom_left.y = fit(self.bottom_left.y, VIEW.y, area.bottom_left.y, top_right.y);
+        self.clamp();
+    }
+
+    /// Screen destination for something drawn at a world position.
+    pub fn dst(&self, bottom_left: glm::IVec2, dims: glm::UVec2) -> Destination {
+        let offset = self.offset();
+        align::left(bottom_left.x - offset.x)
+            .bottom(VIEW.y as i32 - (bottom_left.y - offset.y))
+            .dims(dims)
+    }
+
+    pub fn is_visible(&self, bottom_left: glm::IVec2, dims: glm::UVec2) -> bool {
+        let offset = self.offset();
+        bottom_left.x < offset.x + VIEW.x as i32
+            && bottom_left.x + dims.x as i32 > offset.x
+            && bottom_left.y < offset.y + VIEW.y as i32
+            && bottom_left.y + dims.y as i32 > offset.y
+    }
+
+    fn offset(&self) -> glm::IVec2 {
+        glm::ivec2(
+            self.bottom_left.x.round() as i32,
+            self.bottom_left.y.round() as i32,
+        )
+    }
+
+    fn clamp(&mut self) {
+        let max = self.bounds - VIEW;
+        self.bottom_left.x = self.bottom_left.x.min(max.x).max(0.);
+        self.bottom_left.y = self.bottom_left.y.min(max.y).max(0.);
+    }
+}
+
+/// Area something has to stay inside to fit in one view together with `other`.
+pub fn reach(other: &Rectangle) -> Rectangle {
+    let top_right = other.top_right();
+    Rectangle {
+        bottom_left: top_right - VIEW,
+        dims: other.bottom_left + VIEW - (top_right - VIEW),
+    }
+}
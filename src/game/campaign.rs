@@ -0,0 +1,133 @@
+use crate::{data, game::player, Result};
+
+use failure::format_err;
+
+use std::{ffi::OsStr, fs, path::Path};
+
+//lists the levels of the campaign in the order they are played
+pub const MANIFEST: &str = "media/campaign.yaml";
+
+/// A level loaded from its file, ready to be played.
+pub struct Stage {
+    //file name without extension, used to look up the level's scores
+    pub id: String,
+    pub name: String,
+    //index of the level's background in the game data
+    pub background: usize,
+    //no audio backend yet; kept so levels can already name their track
+    #[allow(dead_code)]
+    pub music: Option<String>,
+    pub level: data::Level,
+}
+
+impl Stage {
+    /// Loads a level, naming it after the name in the file, then the given
+    /// name and finally the file itself.
+    pub fn load(path: &Path, name: Option<&str>, game: &data::Game) -> Result<Self> {
+        let id = id(path)?;
+        let level = data::Level::load(path)?;
+        let background = game
+            .background(level.background.as_ref().map(String::as_str))
+            .map_err(|e| format_err!("{}: {}", path.display(), e))?;
+        let name = level
+            .name
+            .clone()
+            .or_else(|| name.map(str::to_string))
+            .unwrap_or_else(|| id.clone());
+        let music = level.music.clone();
+        Ok(Stage {
+            id,
+            name,
+            background,
+            music,
+            level,
+        })
+    }
+}
+
+/// Id of the level in the given file: the file name without extension.
+pub fn id(path: &Path) -> Result<String> {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .map(str::to_string)
+        .ok_or_else(|| format_err!("{} is not a level file", path.display()))
+}
+
+/// Stages in the order the campaign manifest lists them.
+pub fn stages(campaign: &data::Campaign, game: &data::Game) -> Result<Vec<Stage>> {
+    campaign
+        .stages
+        .iter()
+        .map(|s| Stage::load(Path::new(&s.path), Some(&s.name), game))
+        .collect()
+}
+
+/// Every level file in a directory sorted by file name; levels that are part
+/// of the campaign keep their campaign name.
+pub fn levels(dir: &str, campaign: &data::Campaign, game: &data::Game) -> Result<Vec<Stage>> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    paths.retain(|p| p.extension().map_or(false, |e| e == "yaml"));
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|p| {
+            let name = campaign
+                .stages
+                .iter()
+                .find(|s| Path::new(&s.path) == p.as_path())
+                .map(|s| s.name.as_str());
+            Stage::load(p, name, game)
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy)]
+pub enum Mode {
+    Campaign,
+    //a single level picked from the level select screen
+    Level(usize),
+}
+
+/// How far the player has made it through the campaign.
+#[derive(Clone, Copy)]
+pub struct Progress {
+    pub team: player::Team,
+    pub mode: Mode,
+    pub stage: usize,
+    pub score: u32,
+}
+
+impl Progress {
+    pub fn new(team: player::Team, mode: Mode) -> Self {
+        let stage = match mode {
+            Mode::Campaign => 0,
+            Mode::Level(level) => level,
+        };
+        Progress {
+            team,
+            mode,
+            stage,
+            score: 0,
+        }
+    }
+
+    /// Moves on to the next stage, keeping the score of the one just completed.
+    pub fn advance(self, score: u32) -> Self {
+        Progress {
+            stage: self.stage + 1,
+            score: self.score + score,
+            ..self
+        }
+    }
+
+    /// The stage being played, if there is one left.
+    pub fn current<'a>(&self, stages: &'a [Stage], levels: &'a [Stage]) -> Option<&'a Stage> {
+        match self.mode {
+            Mode::Campaign => stages.get(self.stage),
+            Mode::Level(_) => levels.get(self.stage),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    asset,
+    data::{self, Animators, CatKind},
+    game::{
+        camera::Camera,
+        collision::{Body, Shape},
+        ground::Ground,
+    },
+    utils::HKey as Direction,
+    Result,
+};
+
+use moho::{
+    animation::{animator::Animator, TileSheet},
+    renderer::{options, Destination, Draw, Renderer, Show},
+    texture::Texture,
+};
+
+use std::time::Duration;
+
+const SPEED: f64 = 60.;
+
+struct Patrol {
+    left: f64,
+    right: f64,
+}
+
+pub struct Cat {
+    position: glm::DVec2,
+    dims: data::Dimension,
+    body: Body,
+    patrol: Option<Patrol>,
+    facing: Direction,
+    animator: Animator,
+}
+
+impl Cat {
+    pub fn new(
+        kind: &CatKind,
+        bottom_left: data::Dimension,
+        ground: &Ground,
+        game: &data::Game,
+        animators: &Animators,
+    ) -> Self {
+        let tile = ground.position(bottom_left.into());
+        let position = glm::dvec2(tile.x.into(), tile.y.into());
+        let (patrol, animator) = match *kind {
+            CatKind::Idle => (None, animators.cat_idle.start()),
+            CatKind::Moving(distance) => {
+                let distance = f64::from(distance * ground.tile_size.x);
+                let patrol = Patrol {
+                    left: position.x,
+                    right: position.x + distance,
+                };
+                (Some(patrol), animators.cat_walking.start())
+            }
+        };
+        Cat {
+            position,
+            dims: game.cat.out_size,
+            body: Body::new(&game.cat.body, game.cat.out_size),
+            patrol,
+            facing: Direction::Right,
+            animator,
+        }
+    }
+
+    pub fn update(&mut self, elapsed: Duration) {
+        self.animator.animate(elapsed);
+        let patrol = match self.patrol {
+            Some(ref patrol) => patrol,
+            None => return,
+        };
+        let distance = SPEED * elapsed.as_secs_f64();
+        match self.facing {
+            Direction::Right => {
+                self.position.x += distance;
+                if self.position.x >= patrol.right {
+                    self.position.x = patrol.right;
+                    self.facing = Direction::Left;
+                }
+            }
+            Direction::Left => {
+                self.position.x -= distance;
+                if self.position.x <= patrol.left {
+                    self.position.x = patrol.left;
+                    self.facing = Direction::Right;
+                }
+            }
+        }
+    }
+
+    pub fn center(&self) -> glm::DVec2 {
+        self.position + glm::DVec2::from(self.dims) / 2.
+    }
+
+    /// World shapes of the cat's body.
+    pub fn body(&self) -> Vec<Shape> {
+        let flipped = match self.facing {
+            Direction::Left => true,
+            Direction::Right => false,
+        };
+        self.body.at(self.position, flipped)
+    }
+}
+
+struct Drawn {
+    walking: bool,
+    tile: u32,
+    dst: Destination,
+    flip: Option<options::Flip>,
+}
+
+pub struct Assets<T> {
+    idle: TileSheet<T>,
+    walking: TileSheet<T>,
+    cats: Vec<Drawn>,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        cats: &[Cat],
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let assets = Assets {
+            idle: asset_manager.sheet(asset::Animation::IdleCat)?,
+            walking: asset_manager.sheet(asset::Animation::WalkingCat)?,
+            cats: vec![],
+        };
+        Ok(assets.next(cats, camera))
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(mut self, cats: &[Cat], camera: &Camera) -> Self {
+        self.cats = cats
+            .iter()
+            .map(|c| {
+                let position = glm::ivec2(c.position.x.round() as i32, c.position.y.round() as i32);
+                (c, position)
+            })
+            .filter(|&(c, position)| camera.is_visible(position, c.dims.into()))
+            .map(|(c, position)| Drawn {
+                walking: c.patrol.is_some(),
+                tile: c.animator.frame(),
+                dst: camera.dst(position, c.dims.into()),
+                flip: c.facing.into(),
+            })
+            .collect();
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        self.cats
+            .iter()
+            .map(|c| {
+                let sheet = if c.walking { &self.walking } else { &self.idle };
+                let options = match c.flip {
+                    Some(f) => options::flip(f).at(c.dst),
+                    None => options::at(c.dst),
+                };
+                renderer.draw(&sheet.tile(c.tile), options)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    asset, data,
+    game::{
+        camera::Camera,
+        collision::{Rectangle, Shape},
+        ground::Ground,
+    },
+    Result,
+};
+
+use moho::{
+    renderer::{options, Draw, Renderer, Show},
+    texture::Texture,
+};
+
+use std::{f64::consts::PI, time::Duration};
+
+const ACTIVATION: Duration = Duration::from_millis(600);
+
+pub struct Checkpoint {
+    bottom_left: glm::IVec2,
+    dims: data::Dimension,
+    //time since the checkpoint was activated
+    active: Option<Duration>,
+}
+
+impl Checkpoint {
+    pub fn new(tile: data::Dimension, ground: &Ground, game: &data::Game) -> Self {
+        let tile = ground.position(tile.into());
+        let dims = game.heart.out_size;
+        let padding = (ground.tile_size.x as i32 - dims.x as i32) / 2;
+        Checkpoint {
+            bottom_left: glm::ivec2(tile.x + padding, tile.y),
+            dims,
+            active: None,
+        }
+    }
+
+    pub fn update(&mut self, elapsed: Duration) {
+        if let Some(ref mut active) = self.active {
+            *active += elapsed;
+        }
+    }
+
+    /// Activates the checkpoint if the body touches it for the first time;
+    /// returns where the player should respawn from then on.
+    pub fn activate(&mut self, body: &[Shape]) -> Option<glm::DVec2> {
+        if self.active.is_some() {
+            return None;
+        }
+        //touching has to be with what is drawn, not the full sized heart
+        let (bottom_left, dims) = self.rect();
+        let shape = Shape::Rectangle(Rectangle {
+            bottom_left: glm::dvec2(bottom_left.x.into(), bottom_left.y.into()),
+            dims: glm::dvec2(dims.x.into(), dims.y.into()),
+        });
+        if !body.iter().any(|b| b.overlaps(&shape)) {
+            return None;
+        }
+        self.active = Some(Duration::from_secs(0));
+        Some(glm::dvec2(
+            self.bottom_left.x.into(),
+            self.bottom_left.y.into(),
+        ))
+    }
+
+    /// Inactive checkpoints are drawn at half size; activating one makes it
+    /// swell past its full size before settling.
+    fn scale(&self) -> f64 {
+        match self.active {
+            None => 0.5,
+            Some(active) if active < ACTIVATION => {
+                let progress = active.as_secs_f64() / ACTIVATION.as_secs_f64();
+                1. + 0.5 * (progress * PI).sin()
+            }
+            Some(_) => 1.,
+        }
+    }
+
+    /// Bottom left and size of the checkpoint as drawn, scaled around the
+    /// bottom center so it grows out of the ground.
+    fn rect(&self) -> (glm::IVec2, glm::UVec2) {
+        let full = glm::DVec2::from(self.dims);
+        let scale = self.scale();
+        let dims = glm::uvec2((full.x * scale) as u32, (full.y * scale) as u32);
+        let left = self.bottom_left.x + (self.dims.x as i32 - dims.x as i32) / 2;
+        (glm::ivec2(left, self.bottom_left.y), dims)
+    }
+}
+
+pub struct Assets<T> {
+    texture: T,
+    checkpoints: Vec<(glm::IVec2, glm::UVec2)>,
+    camera: Camera,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        checkpoints: &[Checkpoint],
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let assets = Assets {
+            texture: asset_manager.texture(asset::Texture::Heart)?,
+            checkpoints: vec![],
+            camera: *camera,
+        };
+        Ok(assets.next(checkpoints, camera))
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(mut self, checkpoints: &[Checkpoint], camera: &Camera) -> Self {
+        self.camera = *camera;
+        self.checkpoints = checkpoints.iter().map(Checkpoint::rect).collect();
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        self.checkpoints
+            .iter()
+            .filter(|&&(position, dims)| self.camera.is_visible(position, dims))
+            .map(|&(position, dims)| {
+                let dst = self.camera.dst(position, dims);
+                renderer.draw(&self.texture, options::at(dst))
+            })
+            .collect()
+    }
+}
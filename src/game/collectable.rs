@@ -0,0 +1,160 @@
+use crate::{
+    asset,
+    data::{self, Animators},
+    game::{
+        camera::Camera,
+        collision::{self, Circle, Shape},
+        ground::Ground,
+    },
+    Result,
+};
+
+use moho::{
+    animation::{animator::Animator, TileSheet},
+    renderer::{options, Destination, Draw, Renderer, Show},
+    texture::Texture,
+};
+
+use std::time::Duration;
+
+#[derive(Clone, Copy)]
+pub enum Kind {
+    Gem,
+    Coin,
+}
+
+struct Collectable {
+    kind: Kind,
+    position: glm::IVec2,
+    dims: data::Dimension,
+    score: u32,
+}
+
+impl Collectable {
+    fn new(
+        kind: Kind,
+        tile: data::Dimension,
+        collectable: &data::Collectable,
+        ground: &Ground,
+    ) -> Self {
+        //centered inside its tile
+        let tile = ground.position(tile.into());
+        let dims = collectable.out_size;
+        let padding = (glm::IVec2::from(ground.tile_size) - glm::IVec2::from(dims)) / 2;
+        Collectable {
+            kind,
+            position: tile + padding,
+            dims,
+            score: collectable.score,
+        }
+    }
+
+    fn shape(&self) -> Shape {
+        let dims = glm::DVec2::from(self.dims);
+        Shape::Circle(Circle {
+            center: glm::dvec2(self.position.x.into(), self.position.y.into()) + dims / 2.,
+            radius: dims.x.min(dims.y) / 2.,
+        })
+    }
+}
+
+pub struct Collectables {
+    gem: Animator,
+    coin: Animator,
+    items: Vec<Collectable>,
+}
+
+impl Collectables {
+    pub fn new(
+        level: &data::Level,
+        ground: &Ground,
+        game: &data::Game,
+        animators: &Animators,
+    ) -> Self {
+        let gems = level
+            .gems
+            .iter()
+            .map(|&g| Collectable::new(Kind::Gem, g, &game.gem, ground));
+        let coins = level
+            .coins
+            .iter()
+            .map(|&c| Collectable::new(Kind::Coin, c, &game.coin, ground));
+        Collectables {
+            gem: animators.gem.start(),
+            coin: animators.coin.start(),
+            items: gems.chain(coins).collect(),
+        }
+    }
+
+    pub fn update(&mut self, elapsed: Duration) {
+        self.gem.animate(elapsed);
+        self.coin.animate(elapsed);
+    }
+
+    /// Removes everything touching the body and returns the score they were worth.
+    pub fn collect(&mut self, body: &[Shape]) -> u32 {
+        let mut scored = 0;
+        self.items.retain(|c| {
+            let collected = collision::overlaps(body, &[c.shape()]);
+            if collected {
+                scored += c.score;
+            }
+            !collected
+        });
+        scored
+    }
+}
+
+pub struct Assets<T> {
+    gem: TileSheet<T>,
+    coin: TileSheet<T>,
+    gem_tile: u32,
+    coin_tile: u32,
+    items: Vec<(Kind, Destination)>,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        collectables: &Collectables,
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let assets = Assets {
+            gem: asset_manager.sheet(asset::Animation::Gem)?,
+            coin: asset_manager.sheet(asset::Animation::Coin)?,
+            gem_tile: 0,
+            coin_tile: 0,
+            items: vec![],
+        };
+        Ok(assets.next(collectables, camera))
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(mut self, collectables: &Collectables, camera: &Camera) -> Self {
+        self.gem_tile = collectables.gem.frame();
+        self.coin_tile = collectables.coin.frame();
+        self.items = collectables
+            .items
+            .iter()
+            .filter(|c| camera.is_visible(c.position, c.dims.into()))
+            .map(|c| (c.kind, camera.dst(c.position, c.dims.into())))
+            .collect();
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        self.items
+            .iter()
+            .map(|&(kind, dst)| {
+                let tile = match kind {
+                    Kind::Gem => self.gem.tile(self.gem_tile),
+                    Kind::Coin => self.coin.tile(self.coin_tile),
+                };
+                renderer.draw(&tile, options::at(dst))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,214 @@
+use crate::data;
+
+/// Shapes in `game_data.yaml` are authored on a 100x100 grid laid over the sprite.
+const AUTHORED_SIZE: f64 = 100.;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Rectangle {
+    pub bottom_left: glm::DVec2,
+    pub dims: glm::DVec2,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Circle {
+    pub center: glm::DVec2,
+    pub radius: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Shape {
+    Rectangle(Rectangle),
+    Circle(Circle),
+}
+
+impl Rectangle {
+    pub fn top_right(&self) -> glm::DVec2 {
+        self.bottom_left + self.dims
+    }
+
+    /// Smallest rectangle around both.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        let top_right = self.top_right();
+        let other_top_right = other.top_right();
+        let bottom_left = glm::dvec2(
+            self.bottom_left.x.min(other.bottom_left.x),
+            self.bottom_left.y.min(other.bottom_left.y),
+        );
+        let top_right = glm::dvec2(
+            top_right.x.max(other_top_right.x),
+            top_right.y.max(other_top_right.y),
+        );
+        Rectangle {
+            bottom_left,
+            dims: top_right - bottom_left,
+        }
+    }
+
+    pub fn overlaps(&self, other: &Rectangle) -> bool {
+        let top_right = self.top_right();
+        let other_top_right = other.top_right();
+        self.bottom_left.x < other_top_right.x
+            && other.bottom_left.x < top_right.x
+            && self.bottom_left.y < other_top_right.y
+            && other.bottom_left.y < top_right.y
+    }
+
+    fn closest(&self, point: glm::DVec2) -> glm::DVec2 {
+        let top_right = self.top_right();
+        glm::dvec2(
+            point.x.max(self.bottom_left.x).min(top_right.x),
+            point.y.max(self.bottom_left.y).min(top_right.y),
+        )
+    }
+
+    /// Smallest translation along one axis that moves `self` out of `other`.
+    fn push(&self, other: &Rectangle) -> Option<glm::DVec2> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let top_right = self.top_right();
+        let other_top_right = other.top_right();
+        let x = smallest(
+            other.bottom_left.x - top_right.x,
+            other_top_right.x - self.bottom_left.x,
+        );
+        let y = smallest(
+            other.bottom_left.y - top_right.y,
+            other_top_right.y - self.bottom_left.y,
+        );
+        if x.abs() < y.abs() {
+            Some(glm::dvec2(x, 0.))
+        } else {
+            Some(glm::dvec2(0., y))
+        }
+    }
+}
+
+impl Circle {
+    fn push(&self, rect: &Rectangle) -> Option<glm::DVec2> {
+        let closest = rect.closest(self.center);
+        let distance = self.center - closest;
+        let length = (distance.x * distance.x + distance.y * distance.y).sqrt();
+        if length >= self.radius {
+            None
+        } else if length > 0. {
+            Some(distance * ((self.radius - length) / length))
+        } else {
+            //the center is inside the rectangle; leave through the closest edge
+            let top_right = rect.top_right();
+            let x = smallest(
+                rect.bottom_left.x - self.center.x - self.radius,
+                top_right.x - self.center.x + self.radius,
+            );
+            let y = smallest(
+                rect.bottom_left.y - self.center.y - self.radius,
+                top_right.y - self.center.y + self.radius,
+            );
+            if x.abs() < y.abs() {
+                Some(glm::dvec2(x, 0.))
+            } else {
+                Some(glm::dvec2(0., y))
+            }
+        }
+    }
+}
+
+impl Shape {
+    /// Smallest translation that moves the shape out of the rectangle, if they overlap.
+    pub fn push(&self, rect: &Rectangle) -> Option<glm::DVec2> {
+        match *self {
+            Shape::Rectangle(ref r) => r.push(rect),
+            Shape::Circle(ref c) => c.push(rect),
+        }
+    }
+
+    pub fn overlaps(&self, other: &Shape) -> bool {
+        match (*self, *other) {
+            (Shape::Rectangle(a), Shape::Rectangle(b)) => a.overlaps(&b),
+            (Shape::Circle(c), Shape::Rectangle(r)) | (Shape::Rectangle(r), Shape::Circle(c)) => {
+                c.push(&r).is_some()
+            }
+            (Shape::Circle(a), Shape::Circle(b)) => {
+                let distance = a.center - b.center;
+                let radii = a.radius + b.radius;
+                distance.x * distance.x + distance.y * distance.y < radii * radii
+            }
+        }
+    }
+}
+
+/// A compound shape authored relative to a sprite of a given size.
+pub struct Body {
+    shapes: Vec<data::Shape>,
+    dims: glm::DVec2,
+}
+
+impl Body {
+    pub fn new(shapes: &[data::Shape], dims: data::Dimension) -> Self {
+        Body {
+            shapes: shapes.to_vec(),
+            dims: dims.into(),
+        }
+    }
+
+    /// World shapes for a sprite whose bottom left corner is at `bottom_left`.
+    pub fn at(&self, bottom_left: glm::DVec2, flipped: bool) -> Vec<Shape> {
+        let scale = self.dims / AUTHORED_SIZE;
+        let top = bottom_left.y + self.dims.y;
+        let x = |x: f64, width: f64| {
+            let x = if flipped {
+                AUTHORED_SIZE - x - width
+            } else {
+                x
+            };
+            bottom_left.x + x * scale.x
+        };
+
+        self.shapes
+            .iter()
+            .map(|s| match *s {
+                data::Shape::Rectangle(top_left, dims) => {
+                    let top_left = glm::DVec2::from(top_left);
+                    let dims = glm::DVec2::from(dims);
+                    Shape::Rectangle(Rectangle {
+                        bottom_left: glm::dvec2(
+                            x(top_left.x, dims.x),
+                            top - (top_left.y + dims.y) * scale.y,
+                        ),
+                        dims: glm::dvec2(dims.x * scale.x, dims.y * scale.y),
+                    })
+                }
+                data::Shape::Circle(center, radius) => {
+                    let center = glm::DVec2::from(center);
+                    Shape::Circle(Circle {
+                        center: glm::dvec2(x(center.x, 0.), top - center.y * scale.y),
+                        radius: radius * scale.x.min(scale.y),
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+/// The largest push needed to move any of the shapes out of any of the rectangles.
+pub fn push(shapes: &[Shape], rects: &[Rectangle]) -> Option<glm::DVec2> {
+    shapes
+        .iter()
+        .flat_map(|s| rects.iter().filter_map(move |r| s.push(r)))
+        .fold(None, |largest: Option<glm::DVec2>, push| match largest {
+            Some(l) if l.x * l.x + l.y * l.y >= push.x * push.x + push.y * push.y => Some(l),
+            _ => Some(push),
+        })
+}
+
+pub fn overlaps(a: &[Shape], b: &[Shape]) -> bool {
+    a.iter().any(|a| b.iter().any(|b| a.overlaps(b)))
+}
+
+fn smallest(a: f64, b: f64) -> f64 {
+    if a.abs() < b.abs() {
+        a
+    } else {
+        b
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{asset, game::campaign::Progress, Result};
+
+use moho::{
+    self,
+    font::Font,
+    input,
+    renderer::{align, ColorRGBA},
+    texture::{Image, Texture},
+};
+use sdl2::keyboard::Keycode;
+
+/// Shown once the last level of the campaign has been completed.
+pub struct Ending {
+    score: u32,
+}
+
+impl Ending {
+    pub fn new(progress: &Progress) -> Self {
+        Ending {
+            score: progress.score,
+        }
+    }
+
+    pub fn update(self, input: &input::State) -> moho::State<Self, ()> {
+        if input.did_press_key(Keycode::Return) {
+            moho::State::Quit(())
+        } else {
+            moho::State::Running(self)
+        }
+    }
+}
+
+#[derive(moho::Show)]
+pub struct Assets<T> {
+    husky: Image<T>,
+    duck: Image<T>,
+    heart: Image<T>,
+    title: Image<T>,
+    score: Image<T>,
+    instructions: Image<T>,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        ending: &Ending,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let color = ColorRGBA(255, 255, 0, 255);
+        let center = align::center(640);
+
+        let husky = {
+            let pos = align::right(640 - 32 - 30).middle(360);
+            asset_manager.image(asset::Texture::Husky, pos)?.scale(2)
+        };
+        let duck = {
+            let pos = align::left(640 + 32 + 30).middle(360);
+            asset_manager.image(asset::Texture::Duck, pos)?.scale(2)
+        };
+        let heart = {
+            let pos = center.middle(360);
+            asset_manager.image(asset::Texture::Heart, pos)?.scale(2)
+        };
+
+        let title = {
+            let font = asset_manager.font(asset::Font::KenPixel, 64)?;
+            font.texturize("Together Again!", &color)?
+                .at(center.top(100))
+        };
+
+        let score = {
+            let text = format!("Total Score: {:06}", ending.score);
+            let font = asset_manager.font(asset::Font::Joystix, 32)?;
+            let color = ColorRGBA(255, 255, 255, 255);
+            font.texturize(&text, &color)?.at(center.top(460))
+        };
+
+        let instructions = {
+            let text = "<PRESS ENTER TO GO TO MAIN MENU>";
+            let font = asset_manager.font(asset::Font::KenPixel, 32)?;
+            let height = font.measure(text)?.y as i32;
+            font.texturize(text, &color)?
+                .at(center.bottom(720 - height))
+        };
+
+        Ok(Assets {
+            husky,
+            duck,
+            heart,
+            title,
+            score,
+            instructions,
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use super::running;
+use crate::{
+    asset,
+    game::{ghost, hud::Hud, player, score_repository::Score},
+    Result,
+};
+
+use moho::{
+    self,
+    font::Font,
+    input,
+    renderer::{align, ColorRGBA, Draw, Renderer, Show},
+    texture::{Image, Texture},
+};
+use sdl2::keyboard::Keycode;
+
+const TIME_BONUS: u32 = 10;
+
+pub struct Completed {
+    level: String,
+    name: String,
+    character: Option<player::Kind>,
+    time: u64,
+    score: u32,
+    bonus: u32,
+    par: Option<u32>,
+    //handed over to be saved as the level's ghost
+    run: Option<ghost::Run>,
+}
+
+impl Completed {
+    pub fn new(hud: &Hud, run: Option<ghost::Run>) -> Self {
+        let seconds = hud.remaining().as_secs() as u32;
+        Completed {
+            level: hud.level.clone(),
+            name: hud.name.clone(),
+            character: hud.character,
+            time: hud.taken().as_secs(),
+            score: hud.score,
+            bonus: seconds * TIME_BONUS,
+            par: hud.par,
+            run,
+        }
+    }
+
+    pub fn take_run(&mut self) -> Option<ghost::Run> {
+        self.run.take()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.score + self.bonus
+    }
+
+    /// Quits with the total score once the results have been acknowledged.
+    pub fn update(self, input: &input::State) -> moho::State<Self, Score> {
+        if input.did_press_key(Keycode::Return) {
+            let time = Some(self.time);
+            let score = Score::unnamed(self.total(), &self.level, self.character, time);
+            moho::State::Quit(score)
+        } else {
+            moho::State::Running(self)
+        }
+    }
+}
+
+pub struct Assets<T, F> {
+    game: running::Assets<T, F>,
+    alert: Image<T>,
+    results: Vec<Image<T>>,
+    instructions: Image<T>,
+}
+
+impl<T: Texture, F> Assets<T, F> {
+    pub fn load(
+        completed: &Completed,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+        game: running::Assets<T, F>,
+    ) -> Result<Self> {
+        let font = asset_manager.font(asset::Font::KenPixel, 48)?;
+        let alert = font
+            .texturize(
+                &format!("{} COMPLETE", completed.name.to_uppercase()),
+                &ColorRGBA(255, 255, 0, 255),
+            )?
+            .at(align::top(200).center(640));
+        let instructions = font
+            .texturize("<PRESS ENTER>", &ColorRGBA(255, 255, 255, 255))?
+            .at(align::bottom(520).center(640));
+
+        let results = {
+            let font = asset_manager.font(asset::Font::Joystix, 32)?;
+            let color = ColorRGBA(255, 255, 255, 255);
+            let mut lines = vec![
+                format!("{:<12}{:06}", "Score", completed.score),
+                format!("{:<12}{:06}", "Time Bonus", completed.bonus),
+                format!("{:<12}{:06}", "Total", completed.total()),
+            ];
+            if let Some(par) = completed.par {
+                let label = if completed.total() >= par {
+                    "Par Beaten"
+                } else {
+                    "Par"
+                };
+                lines.push(format!("{:<12}{:06}", label, par));
+            }
+
+            let mut top = alert.dst.bottom() + 10;
+            let mut vec = Vec::with_capacity(lines.len());
+            for l in &lines {
+                let image = font.texturize(l, &color)?.at(align::top(top).center(640));
+                top += image.dst.dims.y as i32;
+                vec.push(image);
+            }
+            vec
+        };
+
+        Ok(Assets {
+            game,
+            alert,
+            results,
+            instructions,
+        })
+    }
+}
+
+impl<R: Renderer, T: Draw<R> + Texture, F> Show<R> for Assets<T, F> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        renderer.show(&self.game)?;
+        super::show_panel(renderer)?;
+        renderer.show(&self.alert)?;
+        renderer.show(&self.results)?;
+        renderer.show(&self.instructions)
+    }
+}
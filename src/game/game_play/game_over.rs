@@ -0,0 +1,25 @@
+use crate::game::{hud::Hud, score_repository::Score};
+
+use moho::{self, input};
+use sdl2::keyboard::Keycode;
+
+pub struct GameOver {
+    score: Score,
+}
+
+impl GameOver {
+    pub fn new(hud: &Hud) -> Self {
+        GameOver {
+            score: Score::unnamed(hud.score, &hud.level, hud.character, None),
+        }
+    }
+
+    /// Quits with the score made before losing every life.
+    pub fn update(self, input: &input::State) -> moho::State<Self, Score> {
+        if input.did_press_key(Keycode::Return) {
+            moho::State::Quit(self.score)
+        } else {
+            moho::State::Running(self)
+        }
+    }
+}
@@ -1,39 +1,174 @@
+mod completed;
+mod game_over;
+mod name_entry;
+mod paused;
 mod running;
 mod timeup;
 
-use self::{running::Running, timeup::TimeUp};
-use crate::{asset, data::Animators, game::player, Result};
+use self::{
+    completed::Completed, game_over::GameOver, name_entry::NameEntry, paused::Paused,
+    running::Running, timeup::TimeUp,
+};
+use crate::{
+    asset, data,
+    data::Animators,
+    game::{
+        campaign::Stage,
+        ghost, player,
+        score_repository::{self, Score},
+    },
+    Result,
+};
 
-use moho::{self, engine::step::fixed, font::Font, input, texture::Texture};
+use moho::{
+    self,
+    engine::step::fixed,
+    font::Font,
+    input,
+    renderer::{align, ColorRGBA, Renderer},
+    texture::Texture,
+};
 
 use std::time::Duration;
 
+pub enum Quit {
+    Menu,
+    //start the same level over
+    Restart,
+    //the level was finished with the given total score
+    Completed(u32),
+}
+
 pub enum GamePlay {
     Running(Running),
+    Paused(Paused),
     TimeUp(TimeUp),
+    GameOver(GameOver),
+    Completed(Completed),
+    NameEntry(NameEntry),
 }
 
 impl GamePlay {
-    pub fn new(kind: player::Kind, animators: &Animators) -> Self {
-        GamePlay::Running(Running::new(kind, animators))
+    pub fn new(
+        team: player::Team,
+        stage: &Stage,
+        game: &data::Game,
+        animators: &Animators,
+    ) -> Self {
+        GamePlay::Running(Running::new(team, stage, game, animators))
     }
 
-    pub fn update(self, input: &input::State, elapsed: Duration) -> moho::State<Self, ()> {
+    /// Whether the level is still being played rather than showing how it ended.
+    pub fn is_playing(&self) -> bool {
+        match *self {
+            GamePlay::Running(_) | GamePlay::Paused(_) => true,
+            _ => false,
+        }
+    }
+
+    /// The run just completed, if it should be considered for the level's ghost.
+    pub fn take_run(&mut self) -> Option<ghost::Run> {
+        match *self {
+            GamePlay::Completed(ref mut c) => c.take_run(),
+            _ => None,
+        }
+    }
+
+    /// Whether the player is being asked for their name.
+    pub fn is_typing(&self) -> bool {
+        match *self {
+            GamePlay::NameEntry(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn update(
+        self,
+        input: &input::State,
+        typed: &str,
+        elapsed: Duration,
+    ) -> moho::State<Self, Quit> {
         match self {
-            GamePlay::Running(r) => moho::State::Running(
-                r.update(input, elapsed)
+            GamePlay::Running(r) if paused::is_toggled(input) => {
+                moho::State::Running(GamePlay::Paused(Paused::new(r)))
+            }
+            GamePlay::Running(r) => {
+                let gameplay = r
+                    .update(input, elapsed)
                     .map(GamePlay::Running)
-                    .catch_quit(|_| GamePlay::TimeUp(TimeUp {})),
-            ),
-            GamePlay::TimeUp(t) => t.update(input).map(GamePlay::TimeUp),
+                    .catch_quit(|q| match q {
+                        running::Quit::TimeUp(hud) => GamePlay::TimeUp(TimeUp::new(&hud)),
+                        running::Quit::GameOver(hud) => GamePlay::GameOver(GameOver::new(&hud)),
+                        running::Quit::Completed(hud, run) => {
+                            GamePlay::Completed(Completed::new(&hud, run))
+                        }
+                    });
+                moho::State::Running(gameplay)
+            }
+            GamePlay::Paused(p) => match p.update(input) {
+                moho::State::Running(p) => moho::State::Running(GamePlay::Paused(p)),
+                moho::State::Quit(paused::Quit::Resume(r)) => {
+                    moho::State::Running(GamePlay::Running(r))
+                }
+                moho::State::Quit(paused::Quit::Restart) => moho::State::Quit(Quit::Restart),
+                moho::State::Quit(paused::Quit::Menu) => moho::State::Quit(Quit::Menu),
+            },
+            GamePlay::TimeUp(t) => match t.update(input) {
+                moho::State::Running(t) => moho::State::Running(GamePlay::TimeUp(t)),
+                moho::State::Quit(score) => enter_name(score, Quit::Menu),
+            },
+            GamePlay::GameOver(g) => match g.update(input) {
+                moho::State::Running(g) => moho::State::Running(GamePlay::GameOver(g)),
+                moho::State::Quit(score) => enter_name(score, Quit::Menu),
+            },
+            GamePlay::Completed(c) => match c.update(input) {
+                moho::State::Running(c) => moho::State::Running(GamePlay::Completed(c)),
+                moho::State::Quit(score) => {
+                    let points = score.points;
+                    enter_name(score, Quit::Completed(points))
+                }
+            },
+            GamePlay::NameEntry(n) => n.update(input, typed).map(GamePlay::NameEntry),
+        }
+    }
+}
+
+/// Asks for a name when the score makes the high score list, otherwise moves
+/// straight on. Nothing is asked when the saved scores cannot be read since
+/// saving would overwrite them.
+fn enter_name(score: Score, then: Quit) -> moho::State<GamePlay, Quit> {
+    match score_repository::get() {
+        Ok(ref scores) if scores.qualifies(&score) => {
+            moho::State::Running(GamePlay::NameEntry(NameEntry::new(score, then)))
+        }
+        Ok(_) => moho::State::Quit(then),
+        Err(e) => {
+            score_repository::report(&e);
+            moho::State::Quit(then)
         }
     }
 }
 
+/// Bordered box the end of game overlays are drawn on.
+fn show_panel(renderer: &mut impl Renderer) -> Result<()> {
+    let dims = glm::uvec2(1080, 360);
+    let pos = align::center(640).middle(360);
+    //border
+    renderer.set_draw_color(ColorRGBA(0, 0, 0, 255));
+    renderer.fill_rects(&[pos.dims(dims)])?;
+    //background
+    renderer.set_draw_color(ColorRGBA(60, 0, 70, 255));
+    renderer.fill_rects(&[pos.dims(glm::uvec2(dims.x - 12, dims.y - 12))])
+}
+
 #[derive(moho::Show)]
 pub enum Assets<T, F> {
     Running(running::Assets<T, F>),
+    Paused(paused::Assets<T, F>),
     TimeUp(timeup::Assets<T, F>),
+    GameOver(timeup::Assets<T, F>),
+    Completed(completed::Assets<T, F>),
+    NameEntry(name_entry::Assets<T, F>),
 }
 
 impl<T: Texture, F: Font<Texture = T>> Assets<T, F> {
@@ -46,14 +181,40 @@ impl<T: Texture, F: Font<Texture = T>> Assets<T, F> {
         match *world {
             GamePlay::Running(ref world) => match self {
                 Assets::Running(r) => r.next(world, step),
+                //a restart replays the same level so the frozen assets still match
+                Assets::Paused(p) => p.resume().next(world, step),
                 _ => running::Assets::load(world, asset_manager),
             }
             .map(Assets::Running),
+            GamePlay::Paused(ref world) => match self {
+                Assets::Paused(p) => Ok(p.next(world)),
+                Assets::Running(r) => paused::Assets::load(world, asset_manager, r),
+                _ => unreachable!("only a running level can be paused"),
+            }
+            .map(Assets::Paused),
             GamePlay::TimeUp(_) => match self {
                 Assets::TimeUp(t) => Ok(t),
-                Assets::Running(r) => timeup::Assets::load(asset_manager, r),
+                Assets::Running(r) => timeup::Assets::load(asset_manager, r, "TIME'S UP"),
+                _ => unreachable!("time can only run out while running"),
             }
             .map(Assets::TimeUp),
+            GamePlay::GameOver(_) => match self {
+                Assets::GameOver(g) => Ok(g),
+                Assets::Running(r) => timeup::Assets::load(asset_manager, r, "GAME OVER"),
+                _ => unreachable!("lives can only run out while running"),
+            }
+            .map(Assets::GameOver),
+            GamePlay::Completed(ref world) => match self {
+                Assets::Completed(c) => Ok(c),
+                Assets::Running(r) => completed::Assets::load(world, asset_manager, r),
+                _ => unreachable!("a level can only be completed while running"),
+            }
+            .map(Assets::Completed),
+            GamePlay::NameEntry(ref world) => match self {
+                Assets::NameEntry(n) => n.next(world),
+                _ => name_entry::Assets::load(world, asset_manager),
+            }
+            .map(Assets::NameEntry),
         }
     }
 }
@@ -67,7 +228,7 @@ impl<T: Texture, F: Font<Texture = T>> Assets<T, F> {
             GamePlay::Running(ref world) => {
                 running::Assets::load(world, asset_manager).map(Assets::Running)
             }
-            _ => unreachable!("cannot load timeup state without a previous running state"),
+            _ => unreachable!("cannot load end of game states without a previous running state"),
         }
     }
 }
@@ -0,0 +1,135 @@
+use super::Quit;
+use crate::{
+    asset,
+    game::{
+        score_repository::{self, Score},
+        text::{self, Text},
+    },
+    Result,
+};
+
+use moho::{
+    self,
+    font::Font,
+    input,
+    renderer::{align, ColorRGBA, Draw, Renderer, Show},
+    texture::{Image, Texture},
+};
+use sdl2::keyboard::Keycode;
+
+use std::rc::Rc;
+
+const MAX_NAME: usize = 6;
+
+/// Asks for a name to go with a score that made the high score list.
+pub struct NameEntry {
+    score: Score,
+    //where the game goes once the score is saved
+    then: Quit,
+}
+
+impl NameEntry {
+    pub fn new(score: Score, then: Quit) -> Self {
+        NameEntry { score, then }
+    }
+
+    /// Adds whatever letters and digits were typed to the name; Enter saves
+    /// the score once there is a name and Escape moves on without saving.
+    pub fn update(mut self, input: &input::State, typed: &str) -> moho::State<Self, Quit> {
+        if input.did_press_key(Keycode::Escape) {
+            return moho::State::Quit(self.then);
+        }
+        if input.did_press_key(Keycode::Return) && !self.score.name.is_empty() {
+            let saved = score_repository::get().and_then(|mut scores| {
+                scores.insert(self.score);
+                score_repository::create(&scores)
+            });
+            if let Err(e) = saved {
+                score_repository::report(&e);
+            }
+            return moho::State::Quit(self.then);
+        }
+        if input.did_press_key(Keycode::Backspace) {
+            self.score.name.pop();
+        }
+        let room = MAX_NAME - self.score.name.len();
+        let typed = typed
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_uppercase())
+            .take(room);
+        self.score.name.extend(typed);
+        moho::State::Running(self)
+    }
+}
+
+impl text::Cached for String {
+    type Value = String;
+
+    fn cached(&self) -> String {
+        self.clone()
+    }
+}
+
+pub struct Assets<T, F> {
+    title: Image<T>,
+    score: Image<T>,
+    name: Image<Text<T, F, String>>,
+    instructions: Image<T>,
+}
+
+impl<T: Texture, F: Font<Texture = T>> Assets<T, F> {
+    pub fn load(
+        entry: &NameEntry,
+        asset_manager: &mut impl asset::Manager<Texture = T, Font = F>,
+    ) -> Result<Self> {
+        let color = ColorRGBA(255, 255, 0, 255);
+        let center = align::center(640);
+
+        let title = asset_manager
+            .font(asset::Font::KenPixel, 64)?
+            .texturize("New High Score!", &color)?
+            .at(center.top(100));
+
+        let font = asset_manager.font(asset::Font::Joystix, 32)?;
+        let score = font
+            .texturize(
+                &format!("{:06}", entry.score.points),
+                &ColorRGBA(255, 255, 255, 255),
+            )?
+            .at(center.top(250));
+        let name = Text::load(entry.score.name.clone(), Rc::clone(&font), |n| {
+            format!("{:_<width$}", n, width = MAX_NAME)
+        })?
+        .at(center.top(350));
+
+        let instructions = {
+            let text = "<Type your name; Enter to save, Esc to skip>";
+            let font = asset_manager.font(asset::Font::KenPixel, 32)?;
+            let height = font.measure(text)?.y as i32;
+            font.texturize(text, &color)?
+                .at(center.bottom(720 - height))
+        };
+
+        Ok(Assets {
+            title,
+            score,
+            name,
+            instructions,
+        })
+    }
+
+    pub fn next(mut self, entry: &NameEntry) -> Result<Self> {
+        self.name.texture.update(entry.score.name.clone())?;
+        Ok(self)
+    }
+}
+
+impl<R: Renderer, T: Draw<R>, F> Show<R> for Assets<T, F> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        renderer.show(&self.title)?;
+        renderer.show(&self.score)?;
+        renderer.show(&self.name)?;
+        renderer.show(&self.instructions)
+    }
+}
@@ -0,0 +1,160 @@
+use super::running::{self, Running};
+use crate::{asset, Result};
+
+use moho::{
+    self,
+    font::Font,
+    input,
+    renderer::{align, ColorRGBA, Draw, Renderer, Show},
+    texture::{Image, Texture},
+};
+use sdl2::keyboard::Keycode;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Choice {
+    Resume,
+    Restart,
+    Quit,
+}
+
+//top to bottom
+const CHOICES: [(Choice, &str); 3] = [
+    (Choice::Resume, "Resume"),
+    (Choice::Restart, "Restart Level"),
+    (Choice::Quit, "Quit to Menu"),
+];
+
+pub enum Quit {
+    Resume(Running),
+    Restart,
+    Menu,
+}
+
+/// Holds on to the running level, untouched, until play resumes.
+pub struct Paused {
+    running: Running,
+    selected: Choice,
+}
+
+impl Paused {
+    pub fn new(running: Running) -> Self {
+        Paused {
+            running,
+            selected: Choice::Resume,
+        }
+    }
+
+    pub fn update(mut self, input: &input::State) -> moho::State<Self, Quit> {
+        if is_toggled(input) {
+            return moho::State::Quit(Quit::Resume(self.running));
+        }
+        if input.did_press_key(Keycode::Down) ^ input.did_press_key(Keycode::Up) {
+            let count = CHOICES.len();
+            let current = CHOICES
+                .iter()
+                .position(|&(c, _)| c == self.selected)
+                .unwrap_or(0);
+            let next = if input.did_press_key(Keycode::Down) {
+                (current + 1) % count
+            } else {
+                (current + count - 1) % count
+            };
+            self.selected = CHOICES[next].0;
+        }
+        if !input.did_press_key(Keycode::Return) {
+            return moho::State::Running(self);
+        }
+        moho::State::Quit(match self.selected {
+            Choice::Resume => Quit::Resume(self.running),
+            Choice::Restart => Quit::Restart,
+            Choice::Quit => Quit::Menu,
+        })
+    }
+}
+
+/// Whether the pause key was pressed; the same keys pause and resume.
+pub fn is_toggled(input: &input::State) -> bool {
+    input.did_press_key(Keycode::Escape) || input.did_press_key(Keycode::P)
+}
+
+struct Entry<T> {
+    choice: Choice,
+    idle: Image<T>,
+    selected: Image<T>,
+}
+
+pub struct Assets<T, F> {
+    game: running::Assets<T, F>,
+    title: Image<T>,
+    options: Vec<Entry<T>>,
+    selected: Choice,
+}
+
+impl<T: Texture, F> Assets<T, F> {
+    pub fn load(
+        paused: &Paused,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+        game: running::Assets<T, F>,
+    ) -> Result<Self> {
+        let yellow = ColorRGBA(255, 255, 0, 255);
+        let white = ColorRGBA(255, 255, 255, 255);
+
+        let title = asset_manager
+            .font(asset::Font::KenPixel, 48)?
+            .texturize("PAUSED", &yellow)?
+            .at(align::top(200).center(640));
+
+        let font = asset_manager.font(asset::Font::KenPixel, 32)?;
+        let mut top = title.dst.bottom() + 20;
+        let mut options = Vec::with_capacity(CHOICES.len());
+        for &(choice, text) in &CHOICES {
+            let idle = font
+                .texturize(text, &white)?
+                .at(align::top(top).center(640));
+            let selected = font
+                .texturize(text, &yellow)?
+                .at(align::top(top).center(640));
+            top = idle.dst.bottom() + 10;
+            options.push(Entry {
+                choice,
+                idle,
+                selected,
+            });
+        }
+
+        Ok(Assets {
+            game,
+            title,
+            options,
+            selected: paused.selected,
+        })
+    }
+}
+
+impl<T, F> Assets<T, F> {
+    pub fn next(mut self, paused: &Paused) -> Self {
+        self.selected = paused.selected;
+        self
+    }
+
+    /// Hands back the frozen frame so the level can carry on drawing from it.
+    pub fn resume(self) -> running::Assets<T, F> {
+        self.game
+    }
+}
+
+impl<R: Renderer, T: Draw<R> + Texture, F> Show<R> for Assets<T, F> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        renderer.show(&self.game)?;
+        super::show_panel(renderer)?;
+        renderer.show(&self.title)?;
+        for option in &self.options {
+            if option.choice == self.selected {
+                renderer.show(&option.selected)?;
+            } else {
+                renderer.show(&option.idle)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,50 +1,280 @@
 use crate::{
     asset,
-    data::Animators,
+    data::{self, Animators},
     game::{
+        background::{self, Background},
+        camera::{self, Camera},
+        campaign::Stage,
+        cat::{self, Cat},
+        checkpoint::{self, Checkpoint},
+        collectable::{self, Collectables},
+        collision::{self, Rectangle},
+        ghost::{self, Ghost},
+        goal::{self, Goal},
+        ground::{self, Ground},
         hud::{self, Hud},
         player::{self, Player},
+        spike::{self, Spike},
     },
     Result,
 };
 
-use moho::{self, engine::step::fixed, font::Font, input, texture::Texture};
+use moho::{
+    self,
+    engine::step::fixed,
+    font::Font,
+    input,
+    renderer::{Draw, Renderer, Show},
+    texture::Texture,
+};
 
 use std::time::Duration;
 
+const HIT_PENALTY: i32 = 50;
+
+pub enum Quit {
+    TimeUp(Hud),
+    GameOver(Hud),
+    //the run comes along for solo players so it can become the level's ghost
+    Completed(Hud, Option<ghost::Run>),
+}
+
 pub struct Running {
     hud: Hud,
-    player: Player,
+    players: Vec<Player>,
+    //which players have made it to the goal; the level needs all of them
+    finished: Vec<bool>,
+    ground: Ground,
+    collectables: Collectables,
+    cats: Vec<Cat>,
+    spikes: Vec<Spike>,
+    goal: Goal,
+    checkpoints: Vec<Checkpoint>,
+    //where players come back after falling out of the level
+    spawn: glm::DVec2,
+    background: Background,
+    camera: Camera,
+    ghost: Option<Ghost>,
+    //every step of a solo run, kept as the level's ghost if it is the fastest
+    trace: Vec<player::Frame>,
 }
 
 impl Running {
-    pub fn new(kind: player::Kind, animators: &Animators) -> Self {
+    pub fn new(
+        team: player::Team,
+        stage: &Stage,
+        game: &data::Game,
+        animators: &Animators,
+    ) -> Self {
+        let level = &stage.level;
+        let ground = Ground::new(&level.obstacles, game.ground.out_size);
+        let spawn = {
+            let tile = ground.position(level.spawn.into());
+            glm::dvec2(tile.x.into(), tile.y.into())
+        };
+        let players: Vec<_> = team
+            .players()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (kind, controls))| {
+                //partners start a couple of tiles apart
+                let offset = f64::from(ground.tile_size.x) * 2. * i as f64;
+                let position = spawn + glm::dvec2(offset, 0.);
+                Player::new(kind, controls, position, game, animators)
+            })
+            .collect();
+        let collectables = Collectables::new(level, &ground, game, animators);
+        let cats = level
+            .cats
+            .iter()
+            .map(|c| Cat::new(&c.kind, c.bottom_left, &ground, game, animators))
+            .collect();
+        let spikes = level
+            .spikes
+            .iter()
+            .map(|s| Spike::new(s, &ground))
+            .collect();
+        let goal = Goal::new(level.goal, &ground, game);
+        let checkpoints = level
+            .checkpoints
+            .iter()
+            .map(|&c| Checkpoint::new(c, &ground, game))
+            .collect();
+        let camera = Camera::new(center(&players), ground.dims());
+        let trace = match players.as_slice() {
+            [player] => vec![player.frame()],
+            _ => vec![],
+        };
         Running {
-            hud: Hud::default(),
-            player: Player::new(kind, animators),
+            hud: Hud::new(stage, team),
+            finished: vec![false; players.len()],
+            players,
+            ground,
+            collectables,
+            cats,
+            spikes,
+            goal,
+            checkpoints,
+            spawn,
+            background: Background::new(stage.background, game),
+            camera,
+            ghost: Ghost::load(&stage.id, game),
+            trace,
+        }
+    }
+
+    pub fn update(mut self, input: &input::State, elapsed: Duration) -> moho::State<Self, Quit> {
+        for i in 0..self.players.len() {
+            let bounds = reach(&self.players, i);
+            self.players[i].update(input, &self.ground, bounds.as_ref(), elapsed);
+        }
+        for cat in &mut self.cats {
+            cat.update(elapsed);
+        }
+        for checkpoint in &mut self.checkpoints {
+            checkpoint.update(elapsed);
+        }
+        self.collectables.update(elapsed);
+        if let Some(ref mut ghost) = self.ghost {
+            ghost.update();
+        }
+
+        let mut scored = 0;
+        for i in 0..self.players.len() {
+            let body = self.players[i].body();
+            for checkpoint in &mut self.checkpoints {
+                if let Some(spawn) = checkpoint.activate(&body) {
+                    self.spawn = spawn;
+                }
+            }
+            scored += self.collectables.collect(&body) as i32;
+            let hazard = self
+                .cats
+                .iter()
+                .find(|c| collision::overlaps(&body, &c.body()))
+                .map(Cat::center)
+                .or_else(|| {
+                    self.spikes
+                        .iter()
+                        .find(|s| collision::overlaps(&body, &[s.shape()]))
+                        .map(Spike::center)
+                });
+            if let Some(hazard) = hazard {
+                if self.damage(i, hazard) {
+                    scored -= HIT_PENALTY;
+                }
+            }
+            if self.players[i].fell() {
+                self.respawn(i);
+                self.hud.lose_life();
+                scored -= HIT_PENALTY;
+            }
+            if collision::overlaps(&body, &[self.goal.shape()]) {
+                self.finished[i] = true;
+            }
+        }
+        self.camera.update(center(&self.players));
+        if let Some(area) = area(&self.players) {
+            self.camera.include(&area);
+        }
+        if let [ref player] = *self.players {
+            self.trace.push(player.frame());
+        }
+        let reached = self.finished.iter().all(|&f| f);
+
+        match self.hud.update(scored, elapsed) {
+            moho::State::Quit(hud) => moho::State::Quit(Quit::TimeUp(hud)),
+            moho::State::Running(hud) if hud.lives == 0 => moho::State::Quit(Quit::GameOver(hud)),
+            moho::State::Running(hud) if reached => {
+                let trace = self.trace;
+                let run = hud
+                    .character
+                    .map(|kind| ghost::Run::new(&hud.level, kind, trace));
+                moho::State::Quit(Quit::Completed(hud, run))
+            }
+            moho::State::Running(hud) => moho::State::Running(Running { hud, ..self }),
         }
     }
 
-    pub fn update(self, input: &input::State, elapsed: Duration) -> moho::State<Self, ()> {
-        let mut player = self.player;
-        player.update(input, elapsed);
+    /// Puts a player back at the last activated checkpoint, or the start of
+    /// the level if there is none; score and collected items are kept.
+    pub fn respawn(&mut self, player: usize) {
+        self.players[player].respawn(self.spawn);
+    }
 
-        self.hud
-            .update(0, elapsed)
-            .map(|hud| Running { hud, player })
+    /// Hurts a player and takes a life unless they are still invincible
+    /// from a previous hit. Returns whether the hit landed.
+    pub fn damage(&mut self, player: usize, from: glm::DVec2) -> bool {
+        let hurt = self.players[player].hurt(from);
+        if hurt {
+            self.hud.lose_life();
+        }
+        hurt
     }
 }
 
-#[derive(moho::Show)]
+/// Midpoint of all the players, which the camera follows.
+fn center(players: &[Player]) -> glm::DVec2 {
+    let sum = players
+        .iter()
+        .fold(glm::dvec2(0., 0.), |sum, p| sum + p.center());
+    sum / players.len() as f64
+}
+
+/// Smallest area covering all the players.
+fn area<'p>(players: impl IntoIterator<Item = &'p Player>) -> Option<Rectangle> {
+    players
+        .into_iter()
+        .map(Player::rectangle)
+        .fold(None, |area, r| {
+            Some(area.map_or(r, |a: Rectangle| a.union(&r)))
+        })
+}
+
+/// Area a player has to stay inside so everyone still fits in the view;
+/// a player on their own can go anywhere.
+fn reach(players: &[Player], player: usize) -> Option<Rectangle> {
+    let others = players
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != player)
+        .map(|(_, p)| p);
+    area(others).map(|others| camera::reach(&others))
+}
+
 pub struct Assets<T, F> {
+    background: background::Assets<T>,
+    ground: ground::Assets<T>,
+    spikes: spike::Assets<T>,
+    goal: goal::Assets<T>,
+    checkpoints: checkpoint::Assets<T>,
+    collectables: collectable::Assets<T>,
+    cats: cat::Assets<T>,
+    ghost: Option<ghost::Assets<T>>,
+    players: Vec<player::Assets<T>>,
     hud: hud::Assets<T, F>,
-    player: player::Assets<T>,
 }
 
 impl<T, F: Font<Texture = T>> Assets<T, F> {
     pub fn next(mut self, world: &Running, _: &fixed::State) -> Result<Self> {
         self.hud = self.hud.next(&world.hud)?;
-        self.player = self.player.next(&world.player);
+        self.background = self.background.next(&world.background, &world.camera);
+        self.ground = self.ground.next(&world.camera);
+        self.spikes = self.spikes.next(&world.camera);
+        self.goal = self.goal.next(&world.camera);
+        self.checkpoints = self.checkpoints.next(&world.checkpoints, &world.camera);
+        self.collectables = self.collectables.next(&world.collectables, &world.camera);
+        self.cats = self.cats.next(&world.cats, &world.camera);
+        self.ghost = match (self.ghost, &world.ghost) {
+            (Some(assets), &Some(ref ghost)) => Some(assets.next(ghost, &world.camera)),
+            _ => None,
+        };
+        self.players = self
+            .players
+            .into_iter()
+            .zip(&world.players)
+            .map(|(assets, player)| assets.next(player, &world.camera))
+            .collect();
         Ok(self)
     }
 }
@@ -55,8 +285,50 @@ impl<T: Texture, F: Font<Texture = T>> Assets<T, F> {
         asset_manager: &mut impl asset::Manager<Texture = T, Font = F>,
     ) -> Result<Self> {
         Ok(Assets {
+            background: background::Assets::load(&world.background, &world.camera, asset_manager)?,
+            ground: ground::Assets::load(&world.ground, &world.camera, asset_manager)?,
+            spikes: spike::Assets::load(&world.spikes, &world.camera, asset_manager)?,
+            goal: goal::Assets::load(&world.goal, &world.camera, asset_manager)?,
+            checkpoints: checkpoint::Assets::load(
+                &world.checkpoints,
+                &world.camera,
+                asset_manager,
+            )?,
+            collectables: collectable::Assets::load(
+                &world.collectables,
+                &world.camera,
+                asset_manager,
+            )?,
+            cats: cat::Assets::load(&world.cats, &world.camera, asset_manager)?,
+            ghost: match world.ghost {
+                Some(ref ghost) => Some(ghost::Assets::load(ghost, &world.camera, asset_manager)?),
+                None => None,
+            },
+            players: world
+                .players
+                .iter()
+                .map(|p| player::Assets::load(p, &world.camera, asset_manager))
+                .collect::<Result<_>>()?,
             hud: hud::Assets::load(&world.hud, asset_manager)?,
-            player: player::Assets::load(&world.player, asset_manager)?,
         })
     }
 }
+
+impl<R: Renderer, T: Draw<R>, F> Show<R> for Assets<T, F> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        renderer.show(&self.background)?;
+        renderer.show(&self.ground)?;
+        renderer.show(&self.spikes)?;
+        renderer.show(&self.goal)?;
+        renderer.show(&self.checkpoints)?;
+        renderer.show(&self.collectables)?;
+        renderer.show(&self.cats)?;
+        if let Some(ref ghost) = self.ghost {
+            renderer.show(ghost)?;
+        }
+        for player in &self.players {
+            renderer.show(player)?;
+        }
+        renderer.show(&self.hud)
+    }
+}
@@ -1,5 +1,9 @@
 use super::running;
-use crate::{asset, Result};
+use crate::{
+    asset,
+    game::{hud::Hud, score_repository::Score},
+    Result,
+};
 
 use moho::{
     self,
@@ -10,12 +14,21 @@ use moho::{
 };
 use sdl2::keyboard::Keycode;
 
-pub struct TimeUp {}
+pub struct TimeUp {
+    score: Score,
+}
 
 impl TimeUp {
-    pub fn update(self, input: &input::State) -> moho::State<Self, ()> {
+    pub fn new(hud: &Hud) -> Self {
+        TimeUp {
+            score: Score::unnamed(hud.score, &hud.level, hud.character, None),
+        }
+    }
+
+    /// Quits with the score made before time ran out.
+    pub fn update(self, input: &input::State) -> moho::State<Self, Score> {
         if input.did_press_key(Keycode::Return) {
-            moho::State::Quit(())
+            moho::State::Quit(self.score)
         } else {
             moho::State::Running(self)
         }
@@ -32,12 +45,13 @@ impl<T: Texture, F> Assets<T, F> {
     pub fn load(
         asset_manager: &mut impl asset::Manager<Texture = T>,
         game: running::Assets<T, F>,
+        alert: &str,
     ) -> Result<Self> {
         let font = asset_manager.font(asset::Font::KenPixel, 48)?;
         Ok(Assets {
             game,
             alert: font
-                .texturize("TIME'S UP", &ColorRGBA(255, 0, 0, 255))?
+                .texturize(alert, &ColorRGBA(255, 0, 0, 255))?
                 .at(align::bottom(360).center(640)),
             instructions: font
                 .texturize("<PRESS ENTER>", &ColorRGBA(255, 255, 255, 255))?
@@ -49,16 +63,7 @@ impl<T: Texture, F> Assets<T, F> {
 impl<R: Renderer, T: Draw<R> + Texture, F> Show<R> for Assets<T, F> {
     fn show(&self, renderer: &mut R) -> Result<()> {
         renderer.show(&self.game)?;
-        {
-            let dims = glm::uvec2(1080, 360);
-            let pos = align::center(640).middle(360);
-            //border
-            renderer.set_draw_color(ColorRGBA(0, 0, 0, 255));
-            renderer.fill_rects(&[pos.dims(dims)])?;
-            //background
-            renderer.set_draw_color(ColorRGBA(60, 0, 70, 255));
-            renderer.fill_rects(&[pos.dims(glm::uvec2(dims.x - 12, dims.y - 12))])?;
-        }
+        super::show_panel(renderer)?;
         renderer.show(&self.alert)?;
         renderer.show(&self.instructions)
     }
@@ -0,0 +1,161 @@
+use crate::{
+    asset, data,
+    game::{
+        camera::Camera,
+        player::{self, Frame},
+    },
+    storage, Result,
+};
+
+use failure::{format_err, ResultExt};
+use moho::{
+    renderer::{Draw, Renderer, Show},
+    texture::Texture,
+};
+
+use std::{fs::File, io, path::PathBuf};
+
+//bump whenever the layout of the ghost file changes
+const VERSION: u32 = 1;
+//see-through enough for the level and the live player to show through
+const ALPHA: u8 = 100;
+
+/// Every step of the fastest solo run of a level.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct Trace {
+    version: u32,
+    kind: player::Kind,
+    frames: Vec<Frame>,
+}
+
+fn path(level: &str) -> Result<PathBuf> {
+    Ok(storage::dir()?
+        .join("ghosts")
+        .join(format!("{}.yaml", level)))
+}
+
+fn load(level: &str) -> Result<Option<Trace>> {
+    let path = path(level)?;
+    let file = match File::open(&path) {
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        file => file.context(format!("could not open {}", path.display()))?,
+    };
+    let trace: Trace =
+        serde_yaml::from_reader(file).context(format!("could not read {}", path.display()))?;
+    if trace.version > VERSION {
+        return Err(format_err!(
+            "{} is version {} but only up to {} is supported",
+            path.display(),
+            trace.version,
+            VERSION
+        ));
+    }
+    Ok(Some(trace))
+}
+
+/// A finished solo run, waiting to be kept as the ghost of its level.
+pub struct Run {
+    level: String,
+    trace: Trace,
+}
+
+impl Run {
+    pub fn new(level: &str, kind: player::Kind, frames: Vec<Frame>) -> Self {
+        Run {
+            level: level.to_string(),
+            trace: Trace {
+                version: VERSION,
+                kind,
+                frames,
+            },
+        }
+    }
+
+    /// Keeps the run as the ghost of its level if it took fewer steps than the
+    /// current one, or the current one cannot be read; failing to do so only
+    /// loses the ghost.
+    pub fn save(self) {
+        let Run { level, trace } = self;
+        let best = load(&level).unwrap_or(None);
+        let saved = match best {
+            Some(ref best) if best.frames.len() <= trace.frames.len() => Ok(()),
+            _ => path(&level).and_then(|path| {
+                storage::write(&path, |file| {
+                    serde_yaml::to_writer(file, &trace).map_err(Into::into)
+                })
+            }),
+        };
+        if let Err(e) = saved {
+            crate::report("ghost", &e);
+        }
+    }
+}
+
+/// The best run of a level, racing the player step by step.
+pub struct Ghost {
+    kind: player::Kind,
+    dims: data::Dimension,
+    frames: Vec<Frame>,
+    step: usize,
+}
+
+impl Ghost {
+    /// The ghost of a level, if anyone has finished it yet.
+    pub fn load(level: &str, game: &data::Game) -> Option<Self> {
+        let trace = load(level).unwrap_or_else(|e| {
+            crate::report("ghost", &e);
+            None
+        })?;
+        let dims = match trace.kind {
+            player::Kind::Husky => game.husky.out_size,
+            player::Kind::Duck => game.duck.out_size,
+        };
+        Some(Ghost {
+            kind: trace.kind,
+            dims,
+            frames: trace.frames,
+            step: 0,
+        })
+    }
+
+    pub fn update(&mut self) {
+        self.step += 1;
+    }
+
+    /// Where the ghost is this step; it vanishes once its run is over.
+    fn frame(&self) -> Option<&Frame> {
+        self.frames.get(self.step)
+    }
+}
+
+pub struct Assets<T> {
+    player: player::Assets<T>,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        ghost: &Ghost,
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let player =
+            player::Assets::load_frame(ghost.kind, ghost.dims, Some(ALPHA), camera, asset_manager)?;
+        Ok(Assets { player }.next(ghost, camera))
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(self, ghost: &Ghost, camera: &Camera) -> Self {
+        let player = match ghost.frame() {
+            Some(frame) => self.player.next_frame(frame, ghost.dims, true, camera),
+            None => self.player.hide(),
+        };
+        Assets { player }
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        renderer.show(&self.player)
+    }
+}
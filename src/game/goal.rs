@@ -0,0 +1,74 @@
+use crate::{
+    asset, data,
+    game::{
+        camera::Camera,
+        collision::{Rectangle, Shape},
+        ground::Ground,
+    },
+    Result,
+};
+
+use moho::{
+    renderer::{options, Draw, Renderer, Show},
+    texture::Texture,
+};
+
+pub struct Goal {
+    bottom_left: glm::IVec2,
+    dims: data::Dimension,
+}
+
+impl Goal {
+    pub fn new(tile: data::Dimension, ground: &Ground, game: &data::Game) -> Self {
+        Goal {
+            bottom_left: ground.position(tile.into()),
+            dims: game.goal.out_size,
+        }
+    }
+
+    pub fn shape(&self) -> Shape {
+        Shape::Rectangle(Rectangle {
+            bottom_left: glm::dvec2(self.bottom_left.x.into(), self.bottom_left.y.into()),
+            dims: self.dims.into(),
+        })
+    }
+}
+
+pub struct Assets<T> {
+    texture: T,
+    bottom_left: glm::IVec2,
+    dims: glm::UVec2,
+    camera: Camera,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        goal: &Goal,
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        Ok(Assets {
+            texture: asset_manager.texture(asset::Texture::Goal)?,
+            bottom_left: goal.bottom_left,
+            dims: goal.dims.into(),
+            camera: *camera,
+        })
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(mut self, camera: &Camera) -> Self {
+        self.camera = *camera;
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        if !self.camera.is_visible(self.bottom_left, self.dims) {
+            return Ok(());
+        }
+        let dst = self.camera.dst(self.bottom_left, self.dims);
+        renderer.draw(&self.texture, options::at(dst))
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    asset, data,
+    game::{camera::Camera, collision::Rectangle},
+    Result,
+};
+
+use moho::{
+    renderer::{options, Draw, Renderer, Show},
+    texture::Texture,
+};
+
+pub struct Ground {
+    count: glm::UVec2,
+    tiles: Vec<bool>,
+    pub tile_size: data::Dimension,
+}
+
+impl Ground {
+    pub fn new(obstacles: &[data::Obstacle], tile_size: data::Dimension) -> Self {
+        let count = obstacles.iter().fold(glm::uvec2(0, 0), |count, o| {
+            let top_right = glm::UVec2::from(o.bottom_left) + glm::UVec2::from(o.count);
+            glm::uvec2(count.x.max(top_right.x), count.y.max(top_right.y))
+        });
+        let mut tiles = vec![false; (count.x * count.y) as usize];
+        for o in obstacles {
+            for y in o.bottom_left.y..o.bottom_left.y + o.count.y {
+                for x in o.bottom_left.x..o.bottom_left.x + o.count.x {
+                    tiles[(y * count.x + x) as usize] = true;
+                }
+            }
+        }
+        Ground {
+            count,
+            tiles,
+            tile_size,
+        }
+    }
+
+    /// Whether the tile at the given index is solid; anything outside the grid is open.
+    pub fn is_solid(&self, tile: glm::IVec2) -> bool {
+        if tile.x < 0
+            || tile.y < 0
+            || tile.x as u32 >= self.count.x
+            || tile.y as u32 >= self.count.y
+        {
+            return false;
+        }
+        self.tiles[(tile.y as u32 * self.count.x + tile.x as u32) as usize]
+    }
+
+    /// Indices of every solid tile, counted from the bottom left of the level.
+    pub fn tiles<'a>(&'a self) -> impl Iterator<Item = glm::IVec2> + 'a {
+        let width = self.count.x;
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|&(_, &solid)| solid)
+            .map(move |(i, _)| glm::ivec2((i as u32 % width) as i32, (i as u32 / width) as i32))
+    }
+
+    /// World position of the bottom left corner of a tile.
+    pub fn position(&self, tile: glm::IVec2) -> glm::IVec2 {
+        glm::ivec2(
+            tile.x * self.tile_size.x as i32,
+            tile.y * self.tile_size.y as i32,
+        )
+    }
+
+    /// Picks the texture for a solid tile based on which of its neighbours are solid.
+    pub fn autotile(&self, tile: glm::IVec2) -> asset::Ground {
+        let above = self.is_solid(glm::ivec2(tile.x, tile.y + 1));
+        let left = self.is_solid(glm::ivec2(tile.x - 1, tile.y));
+        let right = self.is_solid(glm::ivec2(tile.x + 1, tile.y));
+        match (above, left, right) {
+            (false, true, true) => asset::Ground::Top,
+            (false, false, true) => asset::Ground::TopLeft,
+            (false, true, false) => asset::Ground::TopRight,
+            (false, false, false) => asset::Ground::SingleTop,
+            (true, true, true) => asset::Ground::Center,
+            (true, false, true) => asset::Ground::Left,
+            (true, true, false) => asset::Ground::Right,
+            (true, false, false) => asset::Ground::SingleCenter,
+        }
+    }
+
+    /// Solid tiles overlapping the given area.
+    pub fn rectangles(&self, area: &Rectangle) -> Vec<Rectangle> {
+        let dims = glm::DVec2::from(self.tile_size);
+        let top_right = area.top_right();
+        let left = (area.bottom_left.x / dims.x).floor() as i32;
+        let right = (top_right.x / dims.x).floor() as i32;
+        let bottom = (area.bottom_left.y / dims.y).floor() as i32;
+        let top = (top_right.y / dims.y).floor() as i32;
+
+        let mut rectangles = vec![];
+        for y in bottom..=top {
+            for x in left..=right {
+                let tile = glm::ivec2(x, y);
+                if self.is_solid(tile) {
+                    let pos = self.position(tile);
+                    rectangles.push(Rectangle {
+                        bottom_left: glm::dvec2(pos.x.into(), pos.y.into()),
+                        dims,
+                    });
+                }
+            }
+        }
+        rectangles
+    }
+
+    /// Size of the whole level in world units.
+    pub fn dims(&self) -> glm::UVec2 {
+        glm::uvec2(
+            self.count.x * self.tile_size.x,
+            self.count.y * self.tile_size.y,
+        )
+    }
+}
+
+struct Textures<T> {
+    center: T,
+    left: T,
+    right: T,
+    top: T,
+    top_left: T,
+    top_right: T,
+    single_center: T,
+    single_top: T,
+}
+
+impl<T: Texture> Textures<T> {
+    fn load(asset_manager: &mut impl asset::Manager<Texture = T>) -> Result<Self> {
+        let mut texture = |ground| asset_manager.texture(asset::Texture::Ground(ground));
+        Ok(Textures {
+            center: texture(asset::Ground::Center)?,
+            left: texture(asset::Ground::Left)?,
+            right: texture(asset::Ground::Right)?,
+            top: texture(asset::Ground::Top)?,
+            top_left: texture(asset::Ground::TopLeft)?,
+            top_right: texture(asset::Ground::TopRight)?,
+            single_center: texture(asset::Ground::SingleCenter)?,
+            single_top: texture(asset::Ground::SingleTop)?,
+        })
+    }
+}
+
+impl<T> Textures<T> {
+    fn get(&self, ground: asset::Ground) -> &T {
+        match ground {
+            asset::Ground::Center => &self.center,
+            asset::Ground::Left => &self.left,
+            asset::Ground::Right => &self.right,
+            asset::Ground::Top => &self.top,
+            asset::Ground::TopLeft => &self.top_left,
+            asset::Ground::TopRight => &self.top_right,
+            asset::Ground::SingleCenter => &self.single_center,
+            asset::Ground::SingleTop => &self.single_top,
+        }
+    }
+}
+
+pub struct Assets<T> {
+    textures: Textures<T>,
+    tiles: Vec<(asset::Ground, glm::IVec2)>,
+    dims: glm::UVec2,
+    camera: Camera,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        ground: &Ground,
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let textures = Textures::load(asset_manager)?;
+        let tiles = ground
+            .tiles()
+            .map(|t| (ground.autotile(t), ground.position(t)))
+            .collect();
+        Ok(Assets {
+            textures,
+            tiles,
+            dims: ground.tile_size.into(),
+            camera: *camera,
+        })
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(mut self, camera: &Camera) -> Self {
+        self.camera = *camera;
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        self.tiles
+            .iter()
+            .filter(|&&(_, t)| self.camera.is_visible(t, self.dims))
+            .map(|&(ground, t)| {
+                let dst = self.camera.dst(t, self.dims);
+                renderer.draw(self.textures.get(ground), options::at(dst))
+            })
+            .collect()
+    }
+}
@@ -1,9 +1,15 @@
 use crate::{
-    asset::{self, Animation, Font, Sprite, Texture},
+    asset::{self, Alpha, Animation, Font, Sprite, Texture},
     data, Result,
 };
 
-use moho::{animation::TileSheet, font, renderer::Position, texture, texture::Image};
+use moho::{
+    animation::TileSheet,
+    font,
+    renderer::Position,
+    resource::Loader,
+    texture::{self, Image},
+};
 
 use std::rc::Rc;
 
@@ -13,14 +19,16 @@ where
     FL: font::Loader<'f>,
 {
     pub texture_manager: texture::Manager<'t, TL>,
+    //for textures that are not shared through the manager
+    pub texture_loader: &'t TL,
     pub font_manager: font::Manager<'f, FL>,
-    pub data: data::Game,
+    pub data: Rc<data::Game>,
 }
 
 impl<'t, 'f, TL, FL> asset::Manager for Helper<'t, 'f, TL, FL>
 where
     TL: texture::Loader<'t>,
-    TL::Texture: texture::Texture,
+    TL::Texture: texture::Texture + asset::Alpha,
     FL: font::Loader<'f>,
     FL::Font: font::Font<Texture = Rc<TL::Texture>>,
 {
@@ -68,4 +76,26 @@ where
             })
             .map_err(Into::into)
     }
+
+    fn translucent_texture(&mut self, asset: Texture, alpha: u8) -> Result<Self::Texture> {
+        let data = self.data.texture(asset);
+        let mut texture = self
+            .texture_loader
+            .load(&format!("media/sprites/{}", data.texture))?;
+        texture.set_alpha(alpha);
+        Ok(Rc::new(texture))
+    }
+
+    fn translucent_sheet(
+        &mut self,
+        animation: Animation,
+        alpha: u8,
+    ) -> Result<TileSheet<Self::Texture>> {
+        let data = self.data.animation(animation);
+        let mut texture = self
+            .texture_loader
+            .load(&format!("media/sprites/{}", data.texture))?;
+        texture.set_alpha(alpha);
+        Ok(TileSheet::new(data.tiles.into(), Rc::new(texture)))
+    }
 }
@@ -1,18 +1,70 @@
-use crate::{asset, Result};
+use crate::{
+    asset,
+    game::{
+        campaign::Stage,
+        player_select::PlayerKind,
+        score_repository::{self, Score, Scores, MAX_SCORES},
+    },
+    Result,
+};
 
 use moho::{
     self,
     font::Font,
     input,
-    renderer::{align, ColorRGBA},
+    renderer::{align, ColorRGBA, Draw, Renderer, Show},
     texture::{Image, Texture},
 };
 use sdl2::keyboard::Keycode;
 
-pub struct HighScore {}
+#[derive(Clone, Copy, PartialEq)]
+pub enum Tab {
+    Official,
+    Local,
+}
+
+struct Level {
+    id: String,
+    name: String,
+}
+
+/// Leaderboards of every level, one page per level.
+pub struct HighScore {
+    tab: Tab,
+    levels: Vec<Level>,
+    page: usize,
+}
 
 impl HighScore {
-    pub fn update(self, input: &input::State) -> moho::State<Self, ()> {
+    pub fn new(levels: &[Stage]) -> Self {
+        let levels = levels
+            .iter()
+            .map(|l| Level {
+                id: l.id.clone(),
+                name: l.name.clone(),
+            })
+            .collect();
+        HighScore {
+            tab: Tab::Local,
+            levels,
+            page: 0,
+        }
+    }
+
+    pub fn update(mut self, input: &input::State) -> moho::State<Self, ()> {
+        if input.did_press_key(Keycode::Left) {
+            self.tab = Tab::Official;
+        }
+        if input.did_press_key(Keycode::Right) {
+            self.tab = Tab::Local;
+        }
+        let count = self.levels.len();
+        if count > 0 && input.did_press_key(Keycode::Down) {
+            self.page = (self.page + 1) % count;
+        }
+        if count > 0 && input.did_press_key(Keycode::Up) {
+            self.page = (self.page + count - 1) % count;
+        }
         if input.did_press_key(Keycode::Return) {
             moho::State::Quit(())
         } else {
@@ -21,16 +73,45 @@ impl HighScore {
     }
 }
 
-#[derive(moho::Show)]
+enum Lines<T> {
+    //one list per level
+    Pages(Vec<Vec<Image<T>>>),
+    //the scores could not be loaded for any level
+    Error(Vec<Image<T>>),
+}
+
+impl<T> Lines<T> {
+    fn page(&self, page: usize) -> &[Image<T>] {
+        match *self {
+            Lines::Pages(ref pages) => pages.get(page).map_or(&[][..], |p| &p[..]),
+            Lines::Error(ref lines) => lines,
+        }
+    }
+}
+
+struct TabAssets<T> {
+    tab: Tab,
+    idle: Image<T>,
+    selected: Image<T>,
+    lines: Lines<T>,
+}
+
 pub struct Assets<T> {
     title: Image<T>,
     instructions: Image<T>,
-    scores: Vec<Image<T>>,
+    levels: Vec<Image<T>>,
+    tabs: Vec<TabAssets<T>>,
+    selected: Tab,
+    page: usize,
 }
 
 impl<T: Texture> Assets<T> {
-    pub fn load(asset_manager: &mut impl asset::Manager<Texture = T>) -> Result<Self> {
+    pub fn load(
+        high_score: &HighScore,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
         let color = ColorRGBA(255, 255, 0, 255);
+        let white = ColorRGBA(255, 255, 255, 255);
         let center = align::center(640);
 
         let title = {
@@ -40,33 +121,222 @@ impl<T: Texture> Assets<T> {
         };
 
         let instructions = {
-            let text = "<PRESS ENTER TO GO TO MAIN MENU>";
+            let text = "<Left/Right: switch lists; Up/Down: change level; Enter: main menu>";
             let font = asset_manager.font(asset::Font::KenPixel, 32)?;
             let height = font.measure(text)?.y as i32;
             font.texturize(text, &color)?
                 .at(center.bottom(720 - height))
         };
 
-        let scores = {
-            let font = asset_manager.font(asset::Font::Joystix, 32)?;
-            let color = ColorRGBA(255, 255, 255, 255);
-            let scores: Vec<_> = super::score_repository::get();
-
-            let mut top = 150;
-            let mut vec = Vec::with_capacity(scores.len());
-            for s in scores {
-                let score = format!("{:06}{:5}{:>6}", s.points, "", s.name);
-                let image = font.texturize(&score, &color)?.at(center.top(top));
-                top += image.dst.dims.y as i32;
-                vec.push(image);
-            }
-            vec
+        let levels = {
+            let font = asset_manager.font(asset::Font::KenPixel, 40)?;
+            high_score
+                .levels
+                .iter()
+                .map(|l| {
+                    let text = format!("< {} >", l.name);
+                    Ok(font.texturize(&text, &white)?.at(center.top(130)))
+                })
+                .collect::<Result<Vec<_>>>()?
         };
 
+        let official = score_repository::official();
+        let local = score_repository::get();
+
+        let mut tabs = Vec::with_capacity(2);
+        let font = asset_manager.font(asset::Font::KenPixel, 40)?;
+        let lists = [
+            (Tab::Official, "Official", 440, &official),
+            (Tab::Local, "Local", 840, &local),
+        ];
+        for &(tab, text, x, loaded) in &lists {
+            let idle = font.texturize(text, &white)?.at(align::top(80).center(x));
+            let selected = font.texturize(text, &color)?.at(align::top(80).center(x));
+            let lines = match *loaded {
+                Ok(ref scores) => {
+                    let mut pages = Vec::with_capacity(high_score.levels.len());
+                    for l in &high_score.levels {
+                        let lines = match tab {
+                            //the best local score is shown where it would rank among the official ones
+                            Tab::Official => {
+                                let best = local
+                                    .as_ref()
+                                    .ok()
+                                    .and_then(|local| local.top(&l.id, 1).first());
+                                official_lines(scores.top(&l.id, MAX_SCORES), best, asset_manager)?
+                            }
+                            Tab::Local => local_lines(&l.id, scores, asset_manager)?,
+                        };
+                        pages.push(lines);
+                    }
+                    Lines::Pages(pages)
+                }
+                Err(ref e) => Lines::Error(error_lines(e, asset_manager)?),
+            };
+            tabs.push(TabAssets {
+                tab,
+                idle,
+                selected,
+                lines,
+            });
+        }
+
         Ok(Assets {
             title,
             instructions,
-            scores,
+            levels,
+            tabs,
+            selected: high_score.tab,
+            page: high_score.page,
         })
     }
 }
+
+impl<T> Assets<T> {
+    pub fn next(mut self, high_score: &HighScore) -> Self {
+        self.selected = high_score.tab;
+        self.page = high_score.page;
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        renderer.show(&self.title)?;
+        renderer.show(&self.instructions)?;
+        if let Some(level) = self.levels.get(self.page) {
+            renderer.show(level)?;
+        }
+        for tab in &self.tabs {
+            if tab.tab == self.selected {
+                renderer.show(&tab.selected)?;
+                for line in tab.lines.page(self.page) {
+                    renderer.show(line)?;
+                }
+            } else {
+                renderer.show(&tab.idle)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One line per score, with the top local score `marked` highlighted in the
+/// place it would rank.
+fn official_lines<T: Texture>(
+    scores: &[Score],
+    marked: Option<&Score>,
+    asset_manager: &mut impl asset::Manager<Texture = T>,
+) -> Result<Vec<Image<T>>> {
+    let font = asset_manager.font(asset::Font::Joystix, 32)?;
+    let white = ColorRGBA(255, 255, 255, 255);
+    let yellow = ColorRGBA(255, 255, 0, 255);
+    let mut lines: Vec<_> = scores
+        .iter()
+        .map(|s| (format!("{:06}{:5}{:>6}", s.points, "", s.name), &white))
+        .collect();
+    if let Some(marked) = marked {
+        let rank = score_repository::rank(marked.points, scores);
+        let line = format!("{:06}{:^5}{:>6}", marked.points, "*", marked.name);
+        lines.insert(rank, (line, &yellow));
+    }
+
+    let mut top = 190;
+    let mut images = Vec::with_capacity(lines.len() + 1);
+    for (text, color) in lines {
+        let image = font
+            .texturize(&text, color)?
+            .at(align::center(640).top(top));
+        top += image.dst.dims.y as i32;
+        images.push(image);
+    }
+    if marked.is_some() {
+        let font = asset_manager.font(asset::Font::KenPixel, 32)?;
+        images.push(
+            font.texturize("* Top local score", &yellow)?
+                .at(align::center(640).top(600)),
+        );
+    }
+    Ok(images)
+}
+
+/// The best scores made on a level followed by the best made with each character.
+fn local_lines<T: Texture>(
+    level: &str,
+    scores: &Scores,
+    asset_manager: &mut impl asset::Manager<Texture = T>,
+) -> Result<Vec<Image<T>>> {
+    let font = asset_manager.font(asset::Font::Joystix, 32)?;
+    let white = ColorRGBA(255, 255, 255, 255);
+    let yellow = ColorRGBA(255, 255, 0, 255);
+
+    let mut top = 190;
+    let mut images = Vec::with_capacity(MAX_SCORES + 1);
+    for s in scores.top(level, MAX_SCORES) {
+        let time = s
+            .time
+            .map_or_else(|| "---".to_string(), |t| format!("{:03}s", t));
+        let text = format!(
+            "{:06}  {:<6}  {:<5}  {:>4}",
+            s.points,
+            s.name,
+            character(s.character),
+            time
+        );
+        let image = font
+            .texturize(&text, &white)?
+            .at(align::center(640).top(top));
+        top += image.dst.dims.y as i32;
+        images.push(image);
+    }
+
+    let bests = [PlayerKind::Husky, PlayerKind::Duck]
+        .iter()
+        .map(|&kind| {
+            let points = scores
+                .personal_best(level, Some(kind))
+                .map_or_else(|| "------".to_string(), |s| format!("{:06}", s.points));
+            format!("{} {}", character(Some(kind)), points)
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    let font = asset_manager.font(asset::Font::KenPixel, 32)?;
+    let text = format!("Best  {}", bests);
+    images.push(
+        font.texturize(&text, &yellow)?
+            .at(align::center(640).top(600)),
+    );
+    Ok(images)
+}
+
+fn character(character: Option<PlayerKind>) -> &'static str {
+    match character {
+        Some(PlayerKind::Husky) => "Husky",
+        Some(PlayerKind::Duck) => "Duck",
+        //co-op runs and scores saved before characters were recorded
+        None => "--",
+    }
+}
+
+/// One line per cause so long paths still fit on screen.
+fn error_lines<T: Texture>(
+    error: &failure::Error,
+    asset_manager: &mut impl asset::Manager<Texture = T>,
+) -> Result<Vec<Image<T>>> {
+    score_repository::report(error);
+    let font = asset_manager.font(asset::Font::KenPixel, 32)?;
+    let color = ColorRGBA(255, 80, 80, 255);
+    let lines = std::iter::once("Could not load high scores".to_string())
+        .chain(error.iter_chain().map(ToString::to_string));
+
+    let mut top = 190;
+    let mut images = vec![];
+    for text in lines {
+        let image = font
+            .texturize(&text, &color)?
+            .at(align::center(640).top(top));
+        top += image.dst.dims.y as i32;
+        images.push(image);
+    }
+    Ok(images)
+}
@@ -1,42 +1,80 @@
 use crate::{
     asset,
-    game::text::{self, Text},
+    game::{
+        campaign::Stage,
+        player,
+        text::{self, Text},
+    },
     Result,
 };
 
 use moho::{
     self,
     font::Font,
-    renderer::align,
+    renderer::{align, options, ColorRGBA, Draw, Renderer, Show},
     texture::{Image, Texture},
 };
 
 use std::{rc::Rc, time::Duration};
 
 pub struct Hud {
+    //id of the level being played
+    pub level: String,
+    pub name: String,
+    pub character: Option<player::Kind>,
+    limit: Duration,
     timer: Duration,
+    pub par: Option<u32>,
     pub score: u32,
+    pub lives: u32,
 }
 
-impl Default for Hud {
-    fn default() -> Self {
+impl Hud {
+    pub fn new(stage: &Stage, team: player::Team) -> Self {
+        let limit = Duration::from_secs(stage.level.time_limit);
         Hud {
-            timer: Duration::from_secs(100),
+            level: stage.id.clone(),
+            name: stage.name.clone(),
+            character: team.character(),
+            limit,
+            timer: limit,
+            par: stage.level.par_score,
             score: 0,
+            lives: 3,
         }
     }
-}
 
-impl Hud {
-    pub fn update(self, scored: i32, elapsed: Duration) -> moho::State<Self, ()> {
+    pub fn remaining(&self) -> Duration {
+        self.timer
+    }
+
+    /// Time spent on the level so far.
+    pub fn taken(&self) -> Duration {
+        self.limit - self.timer
+    }
+
+    pub fn lose_life(&mut self) {
+        self.lives = self.lives.saturating_sub(1);
+    }
+
+    /// Quits with the final state of the hud once time runs out.
+    pub fn update(self, scored: i32, elapsed: Duration) -> moho::State<Self, Self> {
         let score = if scored >= 0 {
             self.score + scored as u32
         } else {
             self.score.checked_sub(scored.abs() as u32).unwrap_or(0)
         };
         match self.timer.checked_sub(elapsed) {
-            Some(timer) => moho::State::Running(Hud { timer, score }),
-            None => moho::State::Quit(()),
+            Some(timer) => moho::State::Running(Hud {
+                timer,
+                score,
+                ..self
+            }),
+            None => moho::State::Quit(Hud {
+                timer: Duration::from_secs(0),
+                score,
+                ..self
+            }),
         }
     }
 }
@@ -56,26 +94,52 @@ impl text::Cached for u32 {
     }
 }
 
-#[derive(moho::Show)]
 pub struct Assets<T, F> {
+    name: Image<T>,
     timer: Image<Text<T, F, Duration>>,
     score: Image<Text<T, F, u32>>,
+    heart: Image<T>,
+    lives: u32,
 }
 
 impl<T, F: Font<Texture = T>> Assets<T, F> {
     pub fn next(mut self, hud: &Hud) -> Result<Self> {
         self.timer.texture.update(hud.timer)?;
+        self.score.texture.update(hud.score)?;
+        self.lives = hud.lives;
         Ok(self)
     }
 }
 
+impl<R: Renderer, T: Draw<R>, F> Show<R> for Assets<T, F> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        renderer.show(&self.name)?;
+        renderer.show(&self.timer)?;
+        renderer.show(&self.score)?;
+        let first = self.heart.dst;
+        for i in 0..self.lives as i32 {
+            let left = first.left() + i * (first.dims.x as i32 + 6);
+            let dst = align::top(first.top()).left(left).dims(first.dims);
+            renderer.draw(&self.heart.texture, options::at(dst))?;
+        }
+        Ok(())
+    }
+}
+
 impl<T: Texture, F: Font<Texture = T>> Assets<T, F> {
     pub fn load(
         world: &Hud,
         asset_manager: &mut impl asset::Manager<Texture = T, Font = F>,
     ) -> Result<Self> {
         let font = asset_manager.font(asset::Font::KenPixel, 32)?;
+        let heart = asset_manager.image(asset::Texture::Heart, align::top(4).left(10))?;
+        let name = font
+            .texturize(&world.name, &ColorRGBA(255, 255, 255, 255))?
+            .at(align::top(0).center(640));
         Ok(Assets {
+            name,
+            heart,
+            lives: world.lives,
             timer: Text::load(world.timer, Rc::clone(&font), |v| format!("Time: {:03}", v))?
                 .at(align::top(0).center(960)),
             score: Text::load(world.score, font, |v| format!("Score: {:05}", v))?
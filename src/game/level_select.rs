@@ -0,0 +1,159 @@
+use crate::{
+    asset,
+    game::{campaign::Stage, score_repository},
+    Result,
+};
+
+use moho::{
+    self,
+    font::Font,
+    input,
+    renderer::{align, options, ColorRGBA, Draw, Renderer, Show},
+    texture::{Image, Texture},
+};
+use sdl2::keyboard::Keycode;
+
+struct Entry {
+    id: String,
+    name: String,
+}
+
+pub struct LevelSelect {
+    entries: Vec<Entry>,
+    selected: usize,
+}
+
+impl LevelSelect {
+    pub fn new(levels: &[Stage], selected: usize) -> Self {
+        let entries = levels
+            .iter()
+            .map(|l| Entry {
+                id: l.id.clone(),
+                name: l.name.clone(),
+            })
+            .collect();
+        LevelSelect { entries, selected }
+    }
+
+    /// Quits with the index of the chosen level, or `None` to go back to the menu.
+    pub fn update(mut self, input: &input::State) -> moho::State<Self, Option<usize>> {
+        let count = self.entries.len();
+        if count == 0 || input.did_press_key(Keycode::Escape) {
+            return moho::State::Quit(None);
+        }
+        if input.did_press_key(Keycode::Down) {
+            self.selected = (self.selected + 1) % count;
+        }
+        if input.did_press_key(Keycode::Up) {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if input.did_press_key(Keycode::Return) {
+            moho::State::Quit(Some(self.selected))
+        } else {
+            moho::State::Running(self)
+        }
+    }
+}
+
+struct Row<T> {
+    idle: Image<T>,
+    selected: Image<T>,
+}
+
+pub struct Assets<T> {
+    title: Image<T>,
+    instructions: Image<T>,
+    header: Image<T>,
+    rows: Vec<Row<T>>,
+    picker: T,
+    selected: usize,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        level_select: &LevelSelect,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let color = ColorRGBA(255, 255, 0, 255);
+        let center = align::center(640);
+
+        let title = {
+            let font = asset_manager.font(asset::Font::KenPixel, 64)?;
+            font.texturize("Select Level", &color)?.at(center.top(50))
+        };
+
+        let instructions = {
+            let text = "<Use Arrow Keys to choose level; Enter to play; Escape for menu>";
+            let font = asset_manager.font(asset::Font::KenPixel, 32)?;
+            let height = font.measure(text)?.y as i32;
+            font.texturize(text, &color)?
+                .at(center.bottom(720 - height))
+        };
+
+        let font = asset_manager.font(asset::Font::Joystix, 32)?;
+        let white = ColorRGBA(255, 255, 255, 255);
+        let header = font
+            .texturize(
+                &format!("{:<18}{:>8}{:>6}", "Level", "Best", "Time"),
+                &color,
+            )?
+            .at(center.top(160));
+
+        let scores = score_repository::get().unwrap_or_else(|e| {
+            score_repository::report(&e);
+            Default::default()
+        });
+        let mut top = header.dst.bottom() + 10;
+        let mut rows = Vec::with_capacity(level_select.entries.len());
+        for e in &level_select.entries {
+            let best = scores.best(&e.id);
+            let points = best
+                .as_ref()
+                .map_or_else(|| "------".to_string(), |b| format!("{:06}", b.points));
+            let time = best
+                .and_then(|b| b.time)
+                .map_or_else(|| "---".to_string(), |t| format!("{:03}s", t));
+            let text = format!("{:<18}{:>8}{:>6}", e.name, points, time);
+            let idle = font.texturize(&text, &white)?.at(center.top(top));
+            let selected = font.texturize(&text, &color)?.at(center.top(top));
+            top += idle.dst.dims.y as i32;
+            rows.push(Row { idle, selected });
+        }
+
+        Ok(Assets {
+            title,
+            instructions,
+            header,
+            rows,
+            picker: asset_manager.texture(asset::Texture::Heart)?,
+            selected: level_select.selected,
+        })
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(mut self, level_select: &LevelSelect) -> Self {
+        self.selected = level_select.selected;
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R> + Texture> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        renderer.show(&self.title)?;
+        renderer.show(&self.header)?;
+        renderer.show(&self.instructions)?;
+        for (i, row) in self.rows.iter().enumerate() {
+            if i == self.selected {
+                let picker_dst = align::right(row.selected.dst.left() - 10)
+                    .middle(row.selected.dst.middle())
+                    .dims(self.picker.dims());
+                renderer.draw(&self.picker, options::at(picker_dst))?;
+                renderer.show(&row.selected)?;
+            } else {
+                renderer.show(&row.idle)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -10,6 +10,14 @@ use moho::{
 };
 use sdl2::keyboard::Keycode;
 
+//top to bottom
+const BUTTONS: [(button::Kind, &str); 4] = [
+    (button::Kind::NewGame, "New Game"),
+    (button::Kind::Coop, "Co-op"),
+    (button::Kind::LevelSelect, "Level Select"),
+    (button::Kind::HighScore, "High Scores"),
+];
+
 pub struct Gui {
     selected: button::Kind,
 }
@@ -25,10 +33,17 @@ impl Default for Gui {
 impl Gui {
     pub fn update(mut self, input: &input::State) -> moho::State<Self, Quit> {
         if input.did_press_key(Keycode::Down) ^ input.did_press_key(Keycode::Up) {
-            self.selected = match self.selected {
-                button::Kind::NewGame => button::Kind::HighScore,
-                button::Kind::HighScore => button::Kind::NewGame,
-            }
+            let count = BUTTONS.len();
+            let current = BUTTONS
+                .iter()
+                .position(|&(k, _)| k == self.selected)
+                .unwrap_or(0);
+            let next = if input.did_press_key(Keycode::Down) {
+                (current + 1) % count
+            } else {
+                (current + count - 1) % count
+            };
+            self.selected = BUTTONS[next].0;
         }
         if input.did_press_key(Keycode::Return) {
             moho::State::Quit(self.selected)
@@ -40,26 +55,19 @@ impl Gui {
 
 pub struct Assets<T> {
     selected: button::Kind,
-    new_game: button::Assets<T>,
-    high_score: button::Assets<T>,
+    buttons: Vec<(button::Kind, button::Assets<T>)>,
     picker: T,
 }
 
 impl<T> Assets<T> {
     pub fn load(font: &impl Font<Texture = T>, picker: T, gui: &Gui) -> Result<Self> {
-        let new_game = {
-            let center = glm::ivec2(640, 325);
-            let text = "New Game";
-            button::Assets::load(text, font, center)
-        }?;
-        let high_score = {
-            let center = glm::ivec2(640, 500);
-            let text = "High Scores";
-            button::Assets::load(text, font, center)
-        }?;
+        let mut buttons = Vec::with_capacity(BUTTONS.len());
+        for (i, &(kind, text)) in BUTTONS.iter().enumerate() {
+            let center = glm::ivec2(640, 270 + 90 * i as i32);
+            buttons.push((kind, button::Assets::load(text, font, center)?));
+        }
         Ok(Assets {
-            new_game,
-            high_score,
+            buttons,
             picker,
             selected: gui.selected,
         })
@@ -73,22 +81,19 @@ impl<T> Assets<T> {
 
 impl<R: Renderer, T: Draw<R> + Texture> Show<R> for Assets<T> {
     fn show(&self, renderer: &mut R) -> Result<()> {
-        let (selected, unselected) = match self.selected {
-            button::Kind::HighScore => (&self.high_score, &self.new_game),
-            button::Kind::NewGame => (&self.new_game, &self.high_score),
-        };
-        //selected + picker
-        {
-            let picker_dst = align::right(selected.dst.left() - 10)
-                .middle(selected.dst.middle())
-                .dims(self.picker.dims());
-            renderer.draw(&self.picker, options::at(picker_dst))?;
-            renderer.draw(&selected.selected, options::at(selected.dst))
-        }?;
-        //unselected
-        {
-            renderer.draw(&*unselected.idle, options::at(unselected.dst))
+        for &(kind, ref button) in &self.buttons {
+            if kind == self.selected {
+                //selected + picker
+                let picker_dst = align::right(button.dst.left() - 10)
+                    .middle(button.dst.middle())
+                    .dims(self.picker.dims());
+                renderer.draw(&self.picker, options::at(picker_dst))?;
+                renderer.draw(&button.selected, options::at(button.dst))?;
+            } else {
+                renderer.draw(&*button.idle, options::at(button.dst))?;
+            }
         }
+        Ok(())
     }
 }
 
@@ -102,9 +107,11 @@ mod button {
 
     use std::rc::Rc;
 
-    #[derive(Clone, Copy)]
+    #[derive(Clone, Copy, PartialEq)]
     pub enum Kind {
         NewGame,
+        Coop,
+        LevelSelect,
         HighScore,
     }
 
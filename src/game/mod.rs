@@ -1,16 +1,32 @@
+mod background;
+mod camera;
+mod campaign;
+mod cat;
+mod checkpoint;
+mod collectable;
+mod collision;
+mod ending;
 mod game_play;
+mod ghost;
+mod goal;
+mod ground;
 mod helper;
 mod high_score;
 mod hud;
+mod level_select;
 mod menu;
 mod player;
 mod player_select;
+mod replay;
 mod score_repository;
 mod screen;
+mod spike;
+#[cfg(test)]
+mod tests;
 mod text;
 
 use self::{helper::Helper, screen::Screen};
-use crate::{asset, data, Result};
+use crate::{asset, data, typing::Typing, Result};
 
 use moho::{
     self,
@@ -21,34 +37,73 @@ use moho::{
     texture::{self, Texture},
 };
 
-use std::{rc::Rc, time::Duration};
+use std::{path::Path, rc::Rc, time::Duration};
 
 type State<W> = moho::State<W, <W as engine::World>::Quit>;
 
-pub fn run<'t, 'f, C: renderer::Canvas, T: Texture + Draw<C>>(
+pub fn run<'t, 'f, C: renderer::Canvas, T: Texture + Draw<C> + asset::Alpha>(
     engine: &mut Engine<impl input::EventPump, C, fixed::FixedUpdate>,
     texture_loader: &'t impl texture::Loader<'t, Texture = T>,
     font_loader: &'f impl moho::font::Loader<'f, Font = impl moho::font::Font<Texture = Rc<T>>>,
+    typing: Typing,
+    replay: Option<&Path>,
 ) -> Result<()> {
     let font_manager = moho::font::Manager::new(font_loader);
     let texture_manager = texture::Manager::new(texture_loader);
-    let data = data::Game::load("media/game_data.yaml")?;
     let world = World {
-        animators: data.animators(),
-        screen: Screen::new(),
+        typing,
+        ..World::load()?
     };
     let mut helper = Helper {
         font_manager,
         texture_manager,
-        data,
+        texture_loader,
+        data: Rc::clone(&world.data),
     };
-    let scene = Assets::load(&world, &mut helper)?;
-    engine.run(world, scene, helper).map_err(Into::into)
+    match replay {
+        None => {
+            let scene = Assets::load(&world, &mut helper)?;
+            engine.run(world, scene, helper)
+        }
+        Some(path) => {
+            let playback = replay::Playback::new(replay::Replay::load(path)?, world)?;
+            let scene = Assets::load(playback.world(), &mut helper)?;
+            engine.run(playback, scene, helper)
+        }
+    }
+    .map_err(Into::into)
 }
 
 pub struct World {
     screen: Screen,
     animators: data::Animators,
+    data: Rc<data::Game>,
+    stages: Vec<campaign::Stage>,
+    //every level in the levels directory, for the level select screen
+    levels: Vec<campaign::Stage>,
+    //none while playing a replay back
+    recorder: Option<replay::Recorder>,
+    //what the player typed, filled in by the event pump
+    typing: Typing,
+}
+
+impl World {
+    /// Loads the game data and every level, starting at the main menu.
+    fn load() -> Result<Self> {
+        let data = Rc::new(data::Game::load("media/game_data.yaml")?);
+        let manifest = data::Campaign::load(campaign::MANIFEST)?;
+        let stages = campaign::stages(&manifest, &data)?;
+        let levels = campaign::levels("media/levels", &manifest, &data)?;
+        Ok(World {
+            animators: data.animators(),
+            screen: Screen::new(),
+            data,
+            stages,
+            levels,
+            recorder: Some(replay::Recorder::default()),
+            typing: Typing::default(),
+        })
+    }
 }
 
 impl engine::World for World {
@@ -56,19 +111,89 @@ impl engine::World for World {
 
     fn update(self, input: &input::State, elapsed: Duration) -> State<Self> {
         let animators = self.animators;
-        let screen = self.screen.update(input, elapsed).catch_quit(|q| match q {
+        let data = self.data;
+        let stages = self.stages;
+        let levels = self.levels;
+        let mut recorder = self.recorder;
+        let typing = self.typing;
+        if let Some(ref mut recorder) = recorder {
+            if self.screen.is_playing() {
+                recorder.record(input);
+            }
+        }
+        let mut play = |progress: campaign::Progress| match progress.current(&stages, &levels) {
+            Some(stage) => {
+                if let Some(ref mut recorder) = recorder {
+                    recorder.start(&stage.id, progress.team);
+                }
+                Screen::GamePlay(
+                    game_play::GamePlay::new(progress.team, stage, &data, &animators),
+                    progress,
+                )
+            }
+            None => Screen::Ending(ending::Ending::new(&progress)),
+        };
+        let typed = typing.take();
+        let screen = self.screen.update(input, &typed, elapsed);
+        let mut screen = screen.catch_quit(|q| match q {
             screen::Quit::Menu(m) => match m {
-                menu::Quit::NewGame => {
-                    Screen::PlayerSelect(player_select::PlayerSelect::new(&animators))
+                menu::Quit::NewGame => Screen::PlayerSelect(
+                    player_select::PlayerSelect::new(&animators),
+                    campaign::Mode::Campaign,
+                ),
+                menu::Quit::Coop => play(campaign::Progress::new(
+                    player::Team::Coop,
+                    campaign::Mode::Campaign,
+                )),
+                menu::Quit::LevelSelect => {
+                    Screen::LevelSelect(level_select::LevelSelect::new(&levels, 0))
                 }
-                menu::Quit::HighScore => Screen::HighScore(high_score::HighScore {}),
+                menu::Quit::HighScore => Screen::HighScore(high_score::HighScore::new(&levels)),
             },
-            screen::Quit::HighScore | screen::Quit::GamePlay => Screen::Menu(menu::Menu::default()),
-            screen::Quit::PlayerSelect(k) => {
-                Screen::GamePlay(game_play::GamePlay::new(k, &animators))
+            screen::Quit::LevelSelect(Some(level)) => Screen::PlayerSelect(
+                player_select::PlayerSelect::new(&animators),
+                campaign::Mode::Level(level),
+            ),
+            screen::Quit::PlayerSelect(mode, kind) => {
+                play(campaign::Progress::new(player::Team::Solo(kind), mode))
+            }
+            screen::Quit::GamePlay(progress, quit) => match (progress.mode, quit) {
+                (_, game_play::Quit::Restart) => play(progress),
+                (campaign::Mode::Campaign, game_play::Quit::Completed(score)) => {
+                    play(progress.advance(score))
+                }
+                (campaign::Mode::Level(level), _) => {
+                    Screen::LevelSelect(level_select::LevelSelect::new(&levels, level))
+                }
+                (campaign::Mode::Campaign, game_play::Quit::Menu) => {
+                    Screen::Menu(menu::Menu::default())
+                }
+            },
+            screen::Quit::HighScore | screen::Quit::LevelSelect(None) | screen::Quit::Ending => {
+                Screen::Menu(menu::Menu::default())
             }
         });
-        moho::State::Running(World { screen, animators })
+        if let Some(ref mut recorder) = recorder {
+            if !screen.is_playing() {
+                recorder.finish();
+            }
+        }
+        //only runs played live are recorded, and only those become ghosts
+        if let Some(run) = screen.take_run() {
+            if recorder.is_some() {
+                run.save();
+            }
+        }
+        typing.want(screen.is_typing());
+        moho::State::Running(World {
+            screen,
+            animators,
+            data,
+            stages,
+            levels,
+            recorder,
+            typing,
+        })
     }
 }
 
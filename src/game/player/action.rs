@@ -1,60 +1,65 @@
-use crate::{asset::Sprite, utils::HKey as Direction, utils::InputStateExt};
+use crate::{asset::Sprite, utils::HKey};
 
 use moho::{
     animation::{
         animator::{self, Animator},
         TileSheet,
     },
-    input,
+    renderer::Destination,
     texture::Image,
 };
-use sdl2::keyboard::Keycode;
 
 use std::time::Duration;
 
 #[derive(Clone, Copy)]
 pub enum Action {
-    Idle {
-        animator: animator::Data,
-    },
-    Walk {
-        direction: Direction,
-        animator: Animator,
-    },
-    Jump {
-        direction: Option<Direction>,
-        animator: animator::Data,
-    },
+    Idle { animator: animator::Data },
+    Walk { animator: Animator },
+    Jump { animator: animator::Data },
+}
+
+/// How an action looks: which sprite is shown and which tile of the walk cycle.
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+pub enum Pose {
+    Idle,
+    Walk(u32),
+    Jump,
 }
 
 impl Action {
-    pub fn update(self, input: &input::State, elapsed: Duration) -> Self {
-        match (input.is_key_down(Keycode::Space), input.hkey()) {
-            (true, direction) => Action::Jump {
+    pub fn pose(&self) -> Pose {
+        match *self {
+            Action::Idle { .. } => Pose::Idle,
+            Action::Walk { ref animator } => Pose::Walk(animator.frame()),
+            Action::Jump { .. } => Pose::Jump,
+        }
+    }
+
+    pub fn update(self, hkey: Option<HKey>, grounded: bool, elapsed: Duration) -> Self {
+        match (grounded, hkey) {
+            (false, _) => Action::Jump {
                 animator: self.stopped_animator(),
-                direction,
             },
-            (false, None) => Action::Idle {
+            (true, None) => Action::Idle {
                 animator: self.stopped_animator(),
             },
-            (false, Some(direction)) => Action::Walk {
+            (true, Some(_)) => Action::Walk {
                 animator: self.running_animator(elapsed),
-                direction,
             },
         }
     }
 
     fn stopped_animator(self) -> animator::Data {
         match self {
-            Action::Idle { animator } | Action::Jump { animator, .. } => animator,
-            Action::Walk { animator, .. } => animator.stop(),
+            Action::Idle { animator } | Action::Jump { animator } => animator,
+            Action::Walk { animator } => animator.stop(),
         }
     }
 
     fn running_animator(self, elapsed: Duration) -> Animator {
         match self {
-            Action::Idle { animator } | Action::Jump { animator, .. } => animator.start(),
-            Action::Walk { mut animator, .. } => {
+            Action::Idle { animator } | Action::Jump { animator } => animator.start(),
+            Action::Walk { mut animator } => {
                 animator.animate(elapsed);
                 animator
             }
@@ -68,35 +73,28 @@ pub enum Assets<T> {
 }
 
 impl<T> Assets<T> {
-    pub fn next(self, action: &Action) -> Self {
+    pub fn next(self, pose: Pose, dst: Destination) -> Self {
         use self::Assets::*;
 
-        match *action {
-            Action::Idle { .. } | Action::Jump { .. } => match self {
-                Animated(s, texture) => Idle(
+        match pose {
+            Pose::Idle | Pose::Jump => match self {
+                Animated(s, texture) => Idle(Image { texture, dst }, s.sheet),
+                Idle(i, sheet) => Idle(
                     Image {
-                        texture,
-                        dst: s.dst,
+                        texture: i.texture,
+                        dst,
                     },
-                    s.sheet,
+                    sheet,
                 ),
-                idle => idle,
             },
-            Action::Walk { ref animator, .. } => {
-                let tile = animator.frame();
+            Pose::Walk(tile) => {
                 let (sprite, texture) = match self {
                     Animated(mut sprite, texture) => {
                         sprite.tile = tile;
+                        sprite.dst = dst;
                         (sprite, texture)
                     }
-                    Idle(i, sheet) => (
-                        Sprite {
-                            sheet,
-                            tile,
-                            dst: i.dst,
-                        },
-                        i.texture,
-                    ),
+                    Idle(i, sheet) => (Sprite { sheet, tile, dst }, i.texture),
                 };
                 Animated(sprite, texture)
             }
@@ -1,59 +1,332 @@
 mod action;
 
-pub use self::action::Action;
+pub use self::action::{Action, Pose};
 pub use crate::game::player_select::PlayerKind as Kind;
 
-use crate::{asset, data::Animators, utils::HKey as Direction, Result};
+use crate::{
+    asset,
+    data::{self, Animators},
+    game::{
+        camera::Camera,
+        collision::{self, Body, Rectangle},
+        ground::Ground,
+    },
+    utils::{Controls, HKey as Direction, InputStateExt},
+    Result,
+};
 
 use moho::{
     input,
-    renderer::{align, options, Draw, Renderer, Show},
-    texture::Texture,
+    renderer::{options, Destination, Draw, Renderer, Show},
+    texture::{Image, Texture},
 };
 
 use std::time::Duration;
 
+const GRAVITY: f64 = 1800.;
+const JUMP_SPEED: f64 = 720.;
+const ACCELERATION: f64 = 1500.;
+const FRICTION: f64 = 2000.;
+const MAX_SPEED: f64 = 300.;
+const MAX_PUSHES: usize = 4;
+const KNOCKBACK: glm::DVec2 = glm::DVec2 { x: 300., y: 450. };
+const INVINCIBILITY: Duration = Duration::from_secs(2);
+const BLINK: Duration = Duration::from_millis(100);
+
+/// Who is playing: one character of choice, or both characters side by side.
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+pub enum Team {
+    Solo(Kind),
+    Coop,
+}
+
+impl Team {
+    pub fn players(self) -> Vec<(Kind, Controls)> {
+        match self {
+            Team::Solo(kind) => vec![(kind, Controls::ARROWS)],
+            Team::Coop => vec![
+                (Kind::Husky, Controls::ARROWS),
+                (Kind::Duck, Controls::WASD),
+            ],
+        }
+    }
+
+    /// The character playing alone; co-op runs have none.
+    pub fn character(self) -> Option<Kind> {
+        match self {
+            Team::Solo(kind) => Some(kind),
+            Team::Coop => None,
+        }
+    }
+}
+
+/// Where a player is and how they look during one step, as replayed by ghosts.
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+pub struct Frame {
+    pub x: i32,
+    pub y: i32,
+    pub pose: Pose,
+    pub facing: Direction,
+}
+
+impl Frame {
+    fn dst(&self, dims: data::Dimension, camera: &Camera) -> Destination {
+        camera.dst(glm::ivec2(self.x, self.y), dims.into())
+    }
+}
+
 pub struct Player {
     kind: Kind,
+    controls: Controls,
     action: Action,
+    position: glm::DVec2,
+    velocity: glm::DVec2,
+    dims: data::Dimension,
+    facing: Direction,
+    body: Body,
+    legs: Body,
+    grounded: bool,
+    recovering: bool,
+    invincible: Duration,
 }
 
 impl Player {
-    pub fn new(kind: Kind, animators: &Animators) -> Self {
-        let animator = match kind {
-            Kind::Husky => animators.husky,
-            Kind::Duck => animators.duck,
+    pub fn new(
+        kind: Kind,
+        controls: Controls,
+        position: glm::DVec2,
+        game: &data::Game,
+        animators: &Animators,
+    ) -> Self {
+        let (animator, data) = match kind {
+            Kind::Husky => (animators.husky, &game.husky),
+            Kind::Duck => (animators.duck, &game.duck),
         };
         Player {
             kind,
+            controls,
             action: Action::Idle { animator },
+            position,
+            velocity: glm::dvec2(0., 0.),
+            dims: data.out_size,
+            facing: Direction::Right,
+            body: Body::new(&data.body, data.out_size),
+            legs: Body::new(&data.legs, data.out_size),
+            grounded: false,
+            recovering: false,
+            invincible: Duration::from_secs(0),
         }
     }
 
-    pub fn update(&mut self, input: &input::State, elapsed: Duration) {
-        self.action = self.action.update(input, elapsed);
+    /// Moves the player for one step; a player given `bounds` does not move
+    /// out of them, so partners sharing a camera stay in view.
+    pub fn update(
+        &mut self,
+        input: &input::State,
+        ground: &Ground,
+        bounds: Option<&Rectangle>,
+        elapsed: Duration,
+    ) {
+        let delta = elapsed.as_secs_f64();
+        self.invincible = self
+            .invincible
+            .checked_sub(elapsed)
+            .unwrap_or_else(|| Duration::from_secs(0));
+
+        let hkey = input.hkey(&self.controls);
+        //a knocked back player has no control until landing again
+        if !self.recovering {
+            self.velocity.x = match hkey {
+                Some(Direction::Left) => (self.velocity.x - ACCELERATION * delta).max(-MAX_SPEED),
+                Some(Direction::Right) => (self.velocity.x + ACCELERATION * delta).min(MAX_SPEED),
+                None if self.velocity.x > 0. => (self.velocity.x - FRICTION * delta).max(0.),
+                None => (self.velocity.x + FRICTION * delta).min(0.),
+            };
+            if self.grounded && input.did_press_key(self.controls.jump) {
+                self.velocity.y = JUMP_SPEED;
+            }
+            if let Some(direction) = hkey {
+                self.facing = direction;
+            }
+        }
+        self.velocity.y -= GRAVITY * delta;
+        if let Some(bounds) = bounds {
+            self.hold_within(bounds, delta);
+        }
+
+        self.position = self.position + self.velocity * delta;
+        self.grounded = self.collide(ground);
+        if self.grounded {
+            self.recovering = false;
+        }
+        self.action = self.action.update(hkey, self.grounded, elapsed);
+    }
+
+    /// Slows the player down so the next move stays inside `bounds`, leaving
+    /// the ground collision to sort out where they end up. A player already
+    /// outside can only move back in.
+    fn hold_within(&mut self, bounds: &Rectangle, delta: f64) {
+        let min = bounds.bottom_left;
+        let max = bounds.top_right() - glm::DVec2::from(self.dims);
+        let hold = |velocity: f64, position: f64, min: f64, max: f64| {
+            let lowest = ((min - position) / delta).min(0.);
+            let highest = ((max - position) / delta).max(0.);
+            velocity.max(lowest).min(highest)
+        };
+        self.velocity.x = hold(self.velocity.x, self.position.x, min.x, max.x);
+        self.velocity.y = hold(self.velocity.y, self.position.y, min.y, max.y);
+    }
+
+    /// Where the player's sprite covers the level.
+    pub fn rectangle(&self) -> Rectangle {
+        Rectangle {
+            bottom_left: self.position,
+            dims: self.dims.into(),
+        }
+    }
+
+    /// Puts the player back at `position` at rest.
+    pub fn respawn(&mut self, position: glm::DVec2) {
+        self.position = position;
+        self.velocity = glm::dvec2(0., 0.);
+        self.recovering = false;
+        self.grounded = false;
+        self.invincible = INVINCIBILITY;
+    }
+
+    fn is_invincible(&self) -> bool {
+        self.invincible > Duration::from_secs(0)
+    }
+
+    /// Invincible players blink by skipping every other stretch of frames.
+    fn is_visible(&self) -> bool {
+        let blinks = self.invincible.as_millis() / BLINK.as_millis();
+        blinks % 2 == 0
+    }
+
+    /// Whether the player dropped below the bottom of the level.
+    pub fn fell(&self) -> bool {
+        self.position.y + f64::from(self.dims.y) < 0.
+    }
+
+    /// Knocks the player away from `from` and makes them briefly invincible;
+    /// an invincible player is left alone. Returns whether the hit landed.
+    pub fn hurt(&mut self, from: glm::DVec2) -> bool {
+        if self.is_invincible() {
+            return false;
+        }
+        self.invincible = INVINCIBILITY;
+        self.recovering = true;
+        self.grounded = false;
+        self.velocity.x = if from.x > self.center().x {
+            -KNOCKBACK.x
+        } else {
+            KNOCKBACK.x
+        };
+        self.velocity.y = KNOCKBACK.y;
+        true
+    }
+
+    pub fn center(&self) -> glm::DVec2 {
+        self.position + glm::DVec2::from(self.dims) / 2.
+    }
+
+    /// World shapes of the player's body.
+    pub fn body(&self) -> Vec<collision::Shape> {
+        self.body.at(self.position, self.flipped())
+    }
+
+    fn flipped(&self) -> bool {
+        match self.facing {
+            Direction::Left => true,
+            Direction::Right => false,
+        }
+    }
+
+    /// Pushes the player out of the ground: the legs stand on it while the body
+    /// bumps into walls and ceilings. Returns whether the player is standing.
+    fn collide(&mut self, ground: &Ground) -> bool {
+        let mut grounded = false;
+        for &(ref shapes, is_legs) in &[(&self.legs, true), (&self.body, false)] {
+            for _ in 0..MAX_PUSHES {
+                let tiles = ground.rectangles(&self.rectangle());
+                let push = match collision::push(&shapes.at(self.position, self.flipped()), &tiles)
+                {
+                    Some(push) => push,
+                    None => break,
+                };
+                self.position = self.position + push;
+                if push.x.abs() > push.y.abs() {
+                    self.velocity.x = 0.;
+                } else if push.y > 0. {
+                    self.velocity.y = self.velocity.y.max(0.);
+                    grounded |= is_legs;
+                } else {
+                    self.velocity.y = self.velocity.y.min(0.);
+                }
+            }
+        }
+        grounded
+    }
+
+    pub fn frame(&self) -> Frame {
+        Frame {
+            x: self.position.x.round() as i32,
+            y: self.position.y.round() as i32,
+            pose: self.action.pose(),
+            facing: self.facing,
+        }
     }
 }
 
 pub struct Assets<T> {
     action: action::Assets<T>,
     flip: Option<options::Flip>,
+    visible: bool,
 }
 
 impl<T: Texture> Assets<T> {
     pub fn load(
         player: &Player,
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let assets = Assets::load_frame(player.kind, player.dims, None, camera, asset_manager)?;
+        Ok(assets.next(player, camera))
+    }
+
+    /// Assets for a character shown at recorded frames rather than following
+    /// a player, drawn see-through when given an alpha.
+    pub fn load_frame(
+        kind: Kind,
+        dims: data::Dimension,
+        alpha: Option<u8>,
+        camera: &Camera,
         asset_manager: &mut impl asset::Manager<Texture = T>,
     ) -> Result<Self> {
-        let (texture, animation) = match player.kind {
+        let (texture, animation) = match kind {
             Kind::Duck => (asset::Texture::Duck, asset::Animation::Duck),
             Kind::Husky => (asset::Texture::Husky, asset::Animation::Husky),
         };
-        let image = asset_manager.image(texture, align::left(0).top(200))?;
-        let sheet = asset_manager.sheet(animation)?;
-        let action = action::Assets::Idle(image, sheet);
-        let flip = None;
-        Ok(Assets { action, flip })
+        let (texture, sheet) = match alpha {
+            Some(alpha) => (
+                asset_manager.translucent_texture(texture, alpha)?,
+                asset_manager.translucent_sheet(animation, alpha)?,
+            ),
+            None => (
+                asset_manager.texture(texture)?,
+                asset_manager.sheet(animation)?,
+            ),
+        };
+        let image = Image {
+            texture,
+            dst: camera.dst(glm::ivec2(0, 0), dims.into()),
+        };
+        Ok(Assets {
+            action: action::Assets::Idle(image, sheet),
+            flip: None,
+            visible: true,
+        })
     }
 }
 
@@ -67,20 +340,37 @@ impl From<Direction> for Option<options::Flip> {
 }
 
 impl<T> Assets<T> {
-    pub fn next(self, player: &Player) -> Self {
-        let action = self.action.next(&player.action);
-        let flip = match player.action {
-            Action::Idle { .. } => self.flip,
-            Action::Jump { direction, .. } => direction.map(Into::into).unwrap_or(self.flip),
-            Action::Walk { direction, .. } => direction.into(),
-        };
+    pub fn next(self, player: &Player, camera: &Camera) -> Self {
+        self.next_frame(&player.frame(), player.dims, player.is_visible(), camera)
+    }
+
+    pub fn next_frame(
+        self,
+        frame: &Frame,
+        dims: data::Dimension,
+        visible: bool,
+        camera: &Camera,
+    ) -> Self {
+        Assets {
+            action: self.action.next(frame.pose, frame.dst(dims, camera)),
+            flip: frame.facing.into(),
+            visible,
+        }
+    }
 
-        Assets { action, flip }
+    pub fn hide(self) -> Self {
+        Assets {
+            visible: false,
+            ..self
+        }
     }
 }
 
 impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
     fn show(&self, renderer: &mut R) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
         let options = match self.flip {
             Some(f) => options::flip(f),
             None => options::none(),
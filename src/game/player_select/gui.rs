@@ -17,6 +17,7 @@ use sdl2::keyboard::Keycode;
 
 use std::time::Duration;
 
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum ButtonKind {
     Husky,
     Duck,
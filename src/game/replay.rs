@@ -0,0 +1,319 @@
+use crate::{
+    asset,
+    game::{
+        campaign::{Mode, Progress},
+        game_play::GamePlay,
+        player::Team,
+        screen::Screen,
+        Assets, State, World,
+    },
+    storage,
+    utils::Controls,
+    Result,
+};
+
+use failure::{format_err, ResultExt};
+use moho::{
+    engine::{self, step::fixed, NextScene},
+    input,
+};
+use sdl2::{
+    event::Event,
+    keyboard::{Keycode, Mod},
+};
+
+use std::{cell::RefCell, fs::File, path::Path, rc::Rc, time::Duration, vec};
+
+//bump whenever the layout of the replay file changes
+const VERSION: u32 = 1;
+//every key read while a level is being played, in the order of their bit in a step
+const KEYS: [Keycode; 12] = [
+    Controls::ARROWS.left,
+    Controls::ARROWS.right,
+    Keycode::Up,
+    Keycode::Down,
+    Controls::ARROWS.jump,
+    Controls::WASD.left,
+    Controls::WASD.right,
+    Controls::WASD.jump,
+    //menus and pausing
+    Keycode::Return,
+    Keycode::Escape,
+    Keycode::P,
+    Keycode::Backspace,
+];
+
+/// Keys held down and keys just pressed during a single fixed step, one bit per key.
+#[derive(Clone, Copy)]
+struct Step {
+    down: u16,
+    pressed: u16,
+}
+
+impl Step {
+    fn read(input: &input::State) -> Self {
+        KEYS.iter().enumerate().fold(
+            Step {
+                down: 0,
+                pressed: 0,
+            },
+            |step, (i, &key)| Step {
+                down: step.down | u16::from(input.is_key_down(key)) << i,
+                pressed: step.pressed | u16::from(input.did_press_key(key)) << i,
+            },
+        )
+    }
+}
+
+/// Input for every step of one attempt at a level.
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Replay {
+    version: u32,
+    //id of the level played
+    pub level: String,
+    pub team: Team,
+    //runs of identical steps: keys down, keys pressed and how many steps in a row
+    steps: Vec<(u16, u16, u32)>,
+}
+
+impl Replay {
+    fn new(level: &str, team: Team) -> Self {
+        Replay {
+            version: VERSION,
+            level: level.to_string(),
+            team,
+            steps: vec![],
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).context(format!("could not open {}", path.display()))?;
+        let replay: Replay =
+            serde_yaml::from_reader(file).context(format!("could not read {}", path.display()))?;
+        if replay.version > VERSION {
+            return Err(format_err!(
+                "{} is version {} but only up to {} is supported",
+                path.display(),
+                replay.version,
+                VERSION
+            ));
+        }
+        Ok(replay)
+    }
+
+    fn push(&mut self, step: Step) {
+        match self.steps.last_mut() {
+            Some(&mut (down, pressed, ref mut count))
+                if down == step.down && pressed == step.pressed =>
+            {
+                *count += 1
+            }
+            _ => self.steps.push((step.down, step.pressed, 1)),
+        }
+    }
+
+    /// Saves over the last replay of the same level in the data directory.
+    fn save(&self) -> Result<()> {
+        let path = storage::dir()?
+            .join("replays")
+            .join(format!("{}.yaml", self.level));
+        storage::write(&path, |file| {
+            serde_yaml::to_writer(file, self).map_err(Into::into)
+        })
+    }
+}
+
+/// Records the input of every level played, one replay per attempt.
+#[derive(Default)]
+pub struct Recorder {
+    replay: Option<Replay>,
+}
+
+impl Recorder {
+    /// Saves the attempt being recorded, if any, and starts a new one.
+    pub fn start(&mut self, level: &str, team: Team) {
+        self.finish();
+        self.replay = Some(Replay::new(level, team));
+    }
+
+    pub fn record(&mut self, input: &input::State) {
+        if let Some(ref mut replay) = self.replay {
+            replay.push(Step::read(input));
+        }
+    }
+
+    /// Saves the attempt being recorded; failing to do so only loses the replay.
+    pub fn finish(&mut self) {
+        if let Some(replay) = self.replay.take() {
+            if let Err(e) = replay.save() {
+                crate::report("replay", &e);
+            }
+        }
+    }
+}
+
+/// Hands an input manager whatever events were queued since it last asked.
+struct Feed(Rc<RefCell<Vec<Event>>>);
+
+impl input::EventPump for Feed {
+    type I = vec::IntoIter<Event>;
+
+    fn poll_iter(&mut self) -> Self::I {
+        self.0.replace(vec![]).into_iter()
+    }
+}
+
+/// Keyboard pressed in code rather than by a player.
+pub struct Keyboard {
+    manager: input::Manager<Feed>,
+    queue: Rc<RefCell<Vec<Event>>>,
+    down: Vec<Keycode>,
+}
+
+impl Keyboard {
+    pub fn new() -> Self {
+        let queue = Rc::new(RefCell::new(vec![]));
+        Keyboard {
+            manager: input::Manager::new(Feed(Rc::clone(&queue))),
+            queue,
+            down: vec![],
+        }
+    }
+
+    /// Queues the events needed to go from the keys currently down to `down`.
+    pub fn hold(&mut self, down: &[Keycode]) {
+        let mut queue = self.queue.borrow_mut();
+        let released = self.down.iter().filter(|k| !down.contains(k));
+        queue.extend(released.map(|&k| key_event(k, false)));
+        let pressed = down.iter().filter(|k| !self.down.contains(k));
+        queue.extend(pressed.map(|&k| key_event(k, true)));
+        self.down = down.to_vec();
+    }
+
+    /// Input state after the events queued so far.
+    pub fn update(&mut self) -> &input::State {
+        self.manager.update()
+    }
+}
+
+//rebuilds the input state of every recorded step
+struct Input {
+    keyboard: Keyboard,
+    steps: vec::IntoIter<Step>,
+}
+
+/// Plays a level with the input of a replay instead of the player's, quitting
+/// once the replay runs out.
+pub struct Playback {
+    world: World,
+    input: Input,
+}
+
+impl Playback {
+    pub fn new(replay: Replay, mut world: World) -> Result<Self> {
+        let level = world
+            .levels
+            .iter()
+            .position(|l| l.id == replay.level)
+            .ok_or_else(|| format_err!("replay is for unknown level {}", replay.level))?;
+        let gameplay = GamePlay::new(
+            replay.team,
+            &world.levels[level],
+            &world.data,
+            &world.animators,
+        );
+        world.screen = Screen::GamePlay(gameplay, Progress::new(replay.team, Mode::Level(level)));
+        //played back attempts are not recorded again
+        world.recorder = None;
+
+        let steps: Vec<_> = replay
+            .steps
+            .iter()
+            .flat_map(|&(down, pressed, count)| {
+                std::iter::repeat(Step { down, pressed }).take(count as usize)
+            })
+            .collect();
+        let input = Input {
+            keyboard: Keyboard::new(),
+            steps: steps.into_iter(),
+        };
+        Ok(Playback { world, input })
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+}
+
+impl Input {
+    /// Input state for the next recorded step. Keys only count as pressed when
+    /// they were up the state before, so the keys pressed this step are released
+    /// in an extra state first. Keys tapped within the step go down and back up
+    /// in the same state.
+    fn next(&mut self) -> Option<&input::State> {
+        let step = self.steps.next()?;
+        self.keyboard.hold(&keys(step.down & !step.pressed));
+        self.keyboard.update();
+        self.keyboard.hold(&keys(step.down | step.pressed));
+        self.keyboard.hold(&keys(step.down));
+        Some(self.keyboard.update())
+    }
+}
+
+//the keys whose bits are set
+fn keys(bits: u16) -> Vec<Keycode> {
+    KEYS.iter()
+        .enumerate()
+        .filter(|&(i, _)| bits & 1 << i != 0)
+        .map(|(_, &key)| key)
+        .collect()
+}
+
+/// Event for a key going down or coming back up.
+fn key_event(key: Keycode, down: bool) -> Event {
+    let keycode = Some(key);
+    let keymod = Mod::empty();
+    if down {
+        Event::KeyDown {
+            timestamp: 0,
+            window_id: 0,
+            keycode,
+            scancode: None,
+            keymod,
+            repeat: false,
+        }
+    } else {
+        Event::KeyUp {
+            timestamp: 0,
+            window_id: 0,
+            keycode,
+            scancode: None,
+            keymod,
+            repeat: false,
+        }
+    }
+}
+
+impl engine::World for Playback {
+    type Quit = ();
+
+    fn update(self, _: &input::State, elapsed: Duration) -> State<Self> {
+        let Playback { world, mut input } = self;
+        let state = match input.next() {
+            Some(state) => state,
+            None => return moho::State::Quit(()),
+        };
+        let world = engine::World::update(world, state, elapsed);
+        world.map(|world| Playback { world, input })
+    }
+}
+
+impl<AM: asset::Manager> NextScene<Playback, fixed::State, AM> for Assets<AM::Texture, AM::Font>
+where
+    AM::Texture: Clone,
+{
+    fn next(self, playback: &Playback, step: &fixed::State, helper: &mut AM) -> Result<Self> {
+        NextScene::<World, _, _>::next(self, &playback.world, step, helper)
+    }
+}
@@ -1,25 +1,275 @@
-use crate::Result;
+use crate::{
+    data,
+    game::{campaign, player_select::PlayerKind},
+    storage, Result,
+};
 
-use failure::Error;
+use failure::{format_err, ResultExt};
 
-use std::fs::File;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-const PATH: &str = "media/high_scores.yaml";
+//kept in the player's data directory
+const FILE: &str = "high_scores.yaml";
+//copied into the data directory the first time the game runs
+const BUNDLED_PATH: &str = "media/high_scores.yaml";
+//scores shipped with the game; never written to
+const OFFICIAL_PATH: &str = "media/official_scores.yaml";
+//bump whenever the layout of the score file changes
+const VERSION: u32 = 3;
+//how many scores each level keeps
+pub const MAX_SCORES: usize = 10;
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct Score {
     pub points: u32,
     pub name: String,
+    //id of the level the score was made on; the file stores it as the key of the list
+    #[serde(skip)]
+    pub level: String,
+    //who made the score; none for co-op runs and scores from older files
+    #[serde(default)]
+    pub character: Option<PlayerKind>,
+    //seconds taken to complete the level
+    #[serde(default)]
+    pub time: Option<u64>,
+    //seconds since the UNIX epoch when the score was made; zero when unknown
+    #[serde(default)]
+    pub timestamp: u64,
 }
 
-pub fn get() -> Vec<Score> {
-    File::open(PATH)
-        .map_err(Error::from)
-        .and_then(|f| serde_yaml::from_reader(f).map_err(Into::into))
-        .unwrap_or_default()
+impl Score {
+    /// A score for a level waiting for the player to enter their name.
+    pub fn unnamed(
+        points: u32,
+        level: &str,
+        character: Option<PlayerKind>,
+        time: Option<u64>,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Score {
+            points,
+            name: String::new(),
+            level: level.to_string(),
+            character,
+            time,
+            timestamp,
+        }
+    }
 }
 
-pub fn create(entries: &[Score]) -> Result<()> {
-    let file = File::create(PATH)?;
-    serde_yaml::to_writer(file, entries).map_err(Into::into)
+/// Best results recorded for a single level.
+pub struct Best {
+    pub points: u32,
+    pub time: Option<u64>,
+}
+
+/// Every saved score, each level keeping its own list from best to worst.
+#[derive(Default)]
+pub struct Scores {
+    levels: BTreeMap<String, Vec<Score>>,
+    //the best score of each character on each level, even when it fell off the list
+    bests: BTreeMap<String, Vec<Score>>,
+}
+
+impl Scores {
+    /// The best `count` scores made on a level.
+    pub fn top(&self, level: &str, count: usize) -> &[Score] {
+        self.levels
+            .get(level)
+            .map_or(&[][..], |scores| &scores[..count.min(scores.len())])
+    }
+
+    /// The highest score made on a level with the given character.
+    pub fn personal_best(&self, level: &str, character: Option<PlayerKind>) -> Option<&Score> {
+        self.bests
+            .get(level)
+            .and_then(|bests| bests.iter().find(|s| s.character == character))
+    }
+
+    /// Highest score and fastest time among the scores made on a level.
+    pub fn best(&self, level: &str) -> Option<Best> {
+        let scores = self.top(level, MAX_SCORES);
+        scores.first().map(|s| Best {
+            points: s.points,
+            time: scores.iter().filter_map(|s| s.time).min(),
+        })
+    }
+
+    /// Whether a score would be kept, either on the list of its level or as
+    /// the best of its character.
+    pub fn qualifies(&self, score: &Score) -> bool {
+        let scores = self.top(&score.level, MAX_SCORES);
+        let best = self.personal_best(&score.level, score.character);
+        scores.len() < MAX_SCORES
+            || scores.iter().any(|s| score.points > s.points)
+            || best.map_or(true, |b| score.points > b.points)
+    }
+
+    /// Adds a score after every score of its level at least as high, dropping
+    /// whatever falls off the list.
+    pub fn insert(&mut self, score: Score) {
+        self.keep_best(&score);
+        let scores = self.levels.entry(score.level.clone()).or_default();
+        let index = rank(score.points, scores);
+        scores.insert(index, score);
+        scores.truncate(MAX_SCORES);
+    }
+
+    //replaces the best of the score's character if it beats it
+    fn keep_best(&mut self, score: &Score) {
+        let bests = self.bests.entry(score.level.clone()).or_default();
+        match bests.iter().position(|b| b.character == score.character) {
+            Some(i) if bests[i].points >= score.points => {}
+            Some(i) => bests[i] = score.clone(),
+            None => bests.push(score.clone()),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Current<'a> {
+    version: u32,
+    levels: &'a BTreeMap<String, Vec<Score>>,
+    bests: &'a BTreeMap<String, Vec<Score>>,
+}
+
+//a score as saved by version 1 and the unversioned files before it
+#[derive(serde::Deserialize)]
+struct Flat {
+    #[serde(alias = "score")]
+    points: u32,
+    name: String,
+    #[serde(default)]
+    level: Option<String>,
+    #[serde(default)]
+    time: Option<u64>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Stored {
+    Current {
+        version: u32,
+        levels: BTreeMap<String, Vec<Score>>,
+        //version 2 only kept the lists
+        #[serde(default)]
+        bests: BTreeMap<String, Vec<Score>>,
+    },
+    //version 1 kept every level in a single list
+    Versioned {
+        version: u32,
+        scores: Vec<Flat>,
+    },
+    //a bare list of scores
+    Legacy(Vec<Flat>),
+}
+
+/// Reads the saved scores; a missing file just means nothing was saved yet.
+/// Older files are read as is and rewritten in the current format on the next save.
+pub fn get() -> Result<Scores> {
+    load(&storage::seeded(FILE, BUNDLED_PATH)?)
+}
+
+/// Reads the scores that ship with the game.
+pub fn official() -> Result<Scores> {
+    load(Path::new(OFFICIAL_PATH))
+}
+
+fn load(path: &Path) -> Result<Scores> {
+    let file = match File::open(path) {
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Scores::default()),
+        file => file.context(format!("could not open {}", path.display()))?,
+    };
+    let stored: Stored =
+        serde_yaml::from_reader(file).context(format!("could not read {}", path.display()))?;
+    let unsupported = |version| {
+        format_err!(
+            "{} is version {} but only up to {} is supported",
+            path.display(),
+            version,
+            VERSION
+        )
+    };
+    match stored {
+        Stored::Current { version, .. } | Stored::Versioned { version, .. }
+            if version > VERSION =>
+        {
+            Err(unsupported(version))
+        }
+        Stored::Current {
+            mut levels,
+            mut bests,
+            ..
+        } => {
+            for (level, scores) in levels.iter_mut().chain(bests.iter_mut()) {
+                for s in scores {
+                    s.level = level.clone();
+                }
+            }
+            let listed: Vec<_> = levels.values().flatten().cloned().collect();
+            let mut scores = Scores { levels, bests };
+            for s in &listed {
+                scores.keep_best(s);
+            }
+            Ok(scores)
+        }
+        Stored::Versioned { scores, .. } | Stored::Legacy(scores) => migrate(scores),
+    }
+}
+
+fn migrate(flat: Vec<Flat>) -> Result<Scores> {
+    let mut scores = Scores::default();
+    let first_level = first_level()?;
+    for f in flat {
+        scores.insert(Score {
+            points: f.points,
+            name: f.name,
+            level: f.level.unwrap_or_else(|| first_level.clone()),
+            character: None,
+            time: f.time,
+            timestamp: 0,
+        });
+    }
+    Ok(scores)
+}
+
+/// Level the scores saved before they recorded one were made on, back when
+/// the game had a single level: the one the campaign starts with.
+fn first_level() -> Result<String> {
+    let manifest = data::Campaign::load(campaign::MANIFEST)?;
+    campaign::id(Path::new(&manifest.stages[0].path))
+}
+
+/// Logs an error with the score file; the game carries on without the scores.
+pub fn report(error: &failure::Error) {
+    crate::report("high scores", error);
+}
+
+/// Zero based place the given points would take in a list sorted from best to worst.
+pub fn rank(points: u32, scores: &[Score]) -> usize {
+    scores
+        .iter()
+        .position(|s| points > s.points)
+        .unwrap_or_else(|| scores.len())
+}
+
+pub fn create(scores: &Scores) -> Result<()> {
+    let path = storage::dir()?.join(FILE);
+    let current = Current {
+        version: VERSION,
+        levels: &scores.levels,
+        bests: &scores.bests,
+    };
+    storage::write(&path, |file| {
+        serde_yaml::to_writer(file, &current).map_err(Into::into)
+    })
 }
@@ -1,7 +1,11 @@
 use crate::game::{
     asset,
+    campaign::{Mode, Progress},
+    ending::{self, Ending},
     game_play::{self, GamePlay},
+    ghost,
     high_score::{self, HighScore},
+    level_select::{self, LevelSelect},
     menu::{self, Menu},
     player_select::{self, PlayerSelect},
     Result,
@@ -14,8 +18,10 @@ use std::time::Duration;
 pub enum Quit {
     Menu(menu::Quit),
     HighScore,
-    PlayerSelect(player_select::PlayerKind),
-    GamePlay,
+    LevelSelect(Option<usize>),
+    PlayerSelect(Mode, player_select::PlayerKind),
+    GamePlay(Progress, game_play::Quit),
+    Ending,
 }
 
 impl Screen {
@@ -23,21 +29,57 @@ impl Screen {
         Screen::Menu(Menu::default())
     }
 
-    pub fn update(self, input: &input::State, elapsed: Duration) -> moho::State<Self, Quit> {
+    pub fn is_playing(&self) -> bool {
+        match *self {
+            Screen::GamePlay(ref gp, _) => gp.is_playing(),
+            _ => false,
+        }
+    }
+
+    /// A level run just completed that could become the level's ghost.
+    pub fn take_run(&mut self) -> Option<ghost::Run> {
+        match *self {
+            Screen::GamePlay(ref mut gp, _) => gp.take_run(),
+            _ => None,
+        }
+    }
+
+    /// Whether the screen is waiting for the player to type something.
+    pub fn is_typing(&self) -> bool {
+        match *self {
+            Screen::GamePlay(ref gp, _) => gp.is_typing(),
+            _ => false,
+        }
+    }
+
+    pub fn update(
+        self,
+        input: &input::State,
+        typed: &str,
+        elapsed: Duration,
+    ) -> moho::State<Self, Quit> {
         match self {
             Screen::Menu(m) => m.update(input).map(Screen::Menu).map_quit(Quit::Menu),
             Screen::HighScore(hs) => hs
                 .update(input)
                 .map(Screen::HighScore)
                 .map_quit(|_| Quit::HighScore),
-            Screen::PlayerSelect(ps) => ps
-                .update(input, elapsed)
-                .map(Screen::PlayerSelect)
-                .map_quit(Quit::PlayerSelect),
-            Screen::GamePlay(gp) => gp
+            Screen::LevelSelect(ls) => ls
+                .update(input)
+                .map(Screen::LevelSelect)
+                .map_quit(Quit::LevelSelect),
+            Screen::PlayerSelect(ps, mode) => ps
                 .update(input, elapsed)
-                .map(Screen::GamePlay)
-                .map_quit(|_| Quit::GamePlay),
+                .map(|ps| Screen::PlayerSelect(ps, mode))
+                .map_quit(|k| Quit::PlayerSelect(mode, k)),
+            Screen::GamePlay(gp, progress) => gp
+                .update(input, typed, elapsed)
+                .map(|gp| Screen::GamePlay(gp, progress))
+                .map_quit(|q| Quit::GamePlay(progress, q)),
+            Screen::Ending(e) => e
+                .update(input)
+                .map(Screen::Ending)
+                .map_quit(|_| Quit::Ending),
         }
     }
 }
@@ -45,16 +87,20 @@ impl Screen {
 pub enum Screen {
     Menu(Menu),
     HighScore(HighScore),
-    PlayerSelect(PlayerSelect),
-    GamePlay(GamePlay),
+    LevelSelect(LevelSelect),
+    PlayerSelect(PlayerSelect, Mode),
+    GamePlay(GamePlay, Progress),
+    Ending(Ending),
 }
 
 #[derive(moho::Show)]
 pub enum Assets<T, F> {
     Menu(menu::Assets<T>),
     HighScore(high_score::Assets<T>),
+    LevelSelect(level_select::Assets<T>),
     PlayerSelect(player_select::Assets<T>),
     GamePlay(game_play::Assets<T, F>),
+    Ending(ending::Assets<T>),
 }
 
 impl<T: Texture + Clone, F: Font<Texture = T>> Assets<T, F> {
@@ -64,13 +110,19 @@ impl<T: Texture + Clone, F: Font<Texture = T>> Assets<T, F> {
     ) -> Result<Self> {
         match *screen {
             Screen::Menu(ref m) => menu::Assets::load(m, asset_manager).map(Assets::Menu),
-            Screen::HighScore(_) => high_score::Assets::load(asset_manager).map(Assets::HighScore),
-            Screen::PlayerSelect(_) => {
+            Screen::HighScore(ref hs) => {
+                high_score::Assets::load(hs, asset_manager).map(Assets::HighScore)
+            }
+            Screen::LevelSelect(ref ls) => {
+                level_select::Assets::load(ls, asset_manager).map(Assets::LevelSelect)
+            }
+            Screen::PlayerSelect(..) => {
                 player_select::Assets::load(asset_manager).map(Assets::PlayerSelect)
             }
-            Screen::GamePlay(ref gp) => {
+            Screen::GamePlay(ref gp, _) => {
                 game_play::Assets::load(gp, asset_manager).map(Assets::GamePlay)
             }
+            Screen::Ending(ref e) => ending::Assets::load(e, asset_manager).map(Assets::Ending),
         }
     }
 }
@@ -88,21 +140,31 @@ impl<T: Texture + Clone, F: Font<Texture = T>> Assets<T, F> {
                 _ => menu::Assets::load(world, asset_manager),
             }
             .map(Assets::Menu),
-            Screen::HighScore(_) => match self {
-                Assets::HighScore(hs) => Ok(hs),
-                _ => high_score::Assets::load(asset_manager),
+            Screen::HighScore(ref world) => match self {
+                Assets::HighScore(hs) => Ok(hs.next(world)),
+                _ => high_score::Assets::load(world, asset_manager),
             }
             .map(Assets::HighScore),
-            Screen::PlayerSelect(ref world) => match self {
+            Screen::LevelSelect(ref world) => match self {
+                Assets::LevelSelect(ls) => Ok(ls.next(world)),
+                _ => level_select::Assets::load(world, asset_manager),
+            }
+            .map(Assets::LevelSelect),
+            Screen::PlayerSelect(ref world, _) => match self {
                 Assets::PlayerSelect(ps) => Ok(ps.next(world)),
                 _ => player_select::Assets::load(asset_manager),
             }
             .map(Assets::PlayerSelect),
-            Screen::GamePlay(ref world) => match self {
+            Screen::GamePlay(ref world, _) => match self {
                 Assets::GamePlay(ps) => ps.next(world, step, asset_manager),
                 _ => game_play::Assets::load(world, asset_manager),
             }
             .map(Assets::GamePlay),
+            Screen::Ending(ref world) => match self {
+                Assets::Ending(e) => Ok(e),
+                _ => ending::Assets::load(world, asset_manager),
+            }
+            .map(Assets::Ending),
         }
     }
 }
@@ -0,0 +1,138 @@
+use crate::{
+    asset,
+    data::{self, GroundKind},
+    game::{
+        camera::Camera,
+        collision::{Rectangle, Shape},
+        ground::Ground,
+    },
+    Result,
+};
+
+use moho::{
+    renderer::{options, Draw, Renderer, Show},
+    texture::Texture,
+};
+
+/// A row of spikes sitting in a gap of the ground.
+pub struct Spike {
+    bottom_left: glm::IVec2,
+    count: u32,
+    dims: data::Dimension,
+    left: Option<GroundKind>,
+    right: Option<GroundKind>,
+    bottom: Option<GroundKind>,
+}
+
+impl Spike {
+    pub fn new(spike: &data::Spike, ground: &Ground) -> Self {
+        Spike {
+            bottom_left: ground.position(spike.bottom_left.into()),
+            count: spike.count,
+            dims: ground.tile_size,
+            left: spike.left,
+            right: spike.right,
+            bottom: spike.bottom,
+        }
+    }
+
+    /// Only the lower two thirds of the row hurt so grazing the tips is forgiven.
+    pub fn shape(&self) -> Shape {
+        let dims = glm::DVec2::from(self.dims);
+        Shape::Rectangle(Rectangle {
+            bottom_left: glm::dvec2(self.bottom_left.x.into(), self.bottom_left.y.into()),
+            dims: glm::dvec2(dims.x * f64::from(self.count), dims.y * 2. / 3.),
+        })
+    }
+
+    pub fn center(&self) -> glm::DVec2 {
+        let dims = glm::DVec2::from(self.dims);
+        glm::dvec2(
+            f64::from(self.bottom_left.x) + dims.x * f64::from(self.count) / 2.,
+            f64::from(self.bottom_left.y) + dims.y / 2.,
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Piece {
+    Spike,
+    Ground(GroundKind),
+}
+
+pub struct Assets<T> {
+    spike: T,
+    top: T,
+    center: T,
+    dims: glm::UVec2,
+    pieces: Vec<(Piece, glm::IVec2)>,
+    camera: Camera,
+}
+
+impl<T: Texture> Assets<T> {
+    pub fn load(
+        spikes: &[Spike],
+        camera: &Camera,
+        asset_manager: &mut impl asset::Manager<Texture = T>,
+    ) -> Result<Self> {
+        let mut pieces = vec![];
+        for s in spikes {
+            let tile = glm::IVec2::from(s.dims);
+            let width = tile.x * s.count as i32;
+            //ground caps make the neighbouring tiles look like the spikes interrupt them
+            if let Some(kind) = s.left {
+                let position = glm::ivec2(s.bottom_left.x - tile.x, s.bottom_left.y);
+                pieces.push((Piece::Ground(kind), position));
+            }
+            if let Some(kind) = s.right {
+                let position = glm::ivec2(s.bottom_left.x + width, s.bottom_left.y);
+                pieces.push((Piece::Ground(kind), position));
+            }
+            for i in 0..s.count as i32 {
+                let x = s.bottom_left.x + tile.x * i;
+                if let Some(kind) = s.bottom {
+                    let position = glm::ivec2(x, s.bottom_left.y - tile.y);
+                    pieces.push((Piece::Ground(kind), position));
+                }
+                pieces.push((Piece::Spike, glm::ivec2(x, s.bottom_left.y)));
+            }
+        }
+        let dims = spikes
+            .first()
+            .map(|s| s.dims.into())
+            .unwrap_or_else(|| glm::uvec2(0, 0));
+
+        Ok(Assets {
+            spike: asset_manager.texture(asset::Texture::Spike)?,
+            top: asset_manager.texture(asset::Texture::Ground(asset::Ground::Top))?,
+            center: asset_manager.texture(asset::Texture::Ground(asset::Ground::Center))?,
+            dims,
+            pieces,
+            camera: *camera,
+        })
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn next(mut self, camera: &Camera) -> Self {
+        self.camera = *camera;
+        self
+    }
+}
+
+impl<R: Renderer, T: Draw<R>> Show<R> for Assets<T> {
+    fn show(&self, renderer: &mut R) -> Result<()> {
+        self.pieces
+            .iter()
+            .filter(|&&(_, p)| self.camera.is_visible(p, self.dims))
+            .map(|&(piece, p)| {
+                let texture = match piece {
+                    Piece::Spike => &self.spike,
+                    Piece::Ground(GroundKind::Top) => &self.top,
+                    Piece::Ground(GroundKind::Middle) => &self.center,
+                };
+                renderer.draw(texture, options::at(self.camera.dst(p, self.dims)))
+            })
+            .collect()
+    }
+}
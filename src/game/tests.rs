@@ -0,0 +1,210 @@
+use super::{
+    campaign::{Mode, Progress, Stage},
+    game_play::GamePlay,
+    ghost,
+    player::{Frame, Kind, Pose, Team},
+    replay, Assets, Screen, World,
+};
+use crate::{
+    asset, data,
+    headless::{self, Call, Id, Manager, Recorder},
+    storage,
+    utils::HKey,
+};
+
+use moho::{
+    engine::{self, step::fixed, NextScene},
+    renderer::{options::Flip, Show},
+};
+use sdl2::keyboard::Keycode;
+
+use std::{env, fs, io, process, rc::Rc, time::Duration};
+
+/// The game played one fixed step at a time, its scene stepped after every
+/// update the way the engine does it.
+struct Game {
+    world: World,
+    assets: Assets<headless::Texture, headless::Font>,
+    manager: Manager,
+    keyboard: replay::Keyboard,
+}
+
+impl Game {
+    /// Loads the game with a data directory of its own, emptied first so
+    /// scores, replays and ghosts saved by earlier runs are not picked up.
+    fn load(test: &str) -> Self {
+        let dir = env::temp_dir().join(format!("husky_loves_ducky_{}_{}", test, process::id()));
+        match fs::remove_dir_all(&dir) {
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {}
+            removed => removed.unwrap(),
+        }
+        storage::use_dir(dir);
+        Game::new(World::load().unwrap())
+    }
+
+    fn new(world: World) -> Self {
+        let mut manager = Manager {
+            data: Rc::clone(&world.data),
+        };
+        let assets = Assets::load(&world, &mut manager).unwrap();
+        Game {
+            world,
+            assets,
+            manager,
+            keyboard: replay::Keyboard::new(),
+        }
+    }
+
+    /// Starts a level made for the test, skipping the menus.
+    fn play(self, id: &str, level: &str, team: Team) -> Self {
+        let mut world = self.world;
+        let stage = Stage {
+            id: id.to_string(),
+            name: id.to_string(),
+            background: 0,
+            music: None,
+            level: serde_yaml::from_str::<data::Level>(level).unwrap(),
+        };
+        let gameplay = GamePlay::new(team, &stage, &world.data, &world.animators);
+        world.screen = Screen::GamePlay(gameplay, Progress::new(team, Mode::Level(0)));
+        Game::new(world)
+    }
+
+    /// Runs one step with the given keys just pressed and every other key up.
+    fn press(self, keys: &[Keycode]) -> Self {
+        let Game {
+            world,
+            assets,
+            mut manager,
+            mut keyboard,
+        } = self;
+        keyboard.hold(&[]);
+        keyboard.update();
+        keyboard.hold(keys);
+        //the engine steps 30 times a second
+        let step = Duration::from_secs(1) / 30;
+        let world = match engine::World::update(world, keyboard.update(), step) {
+            moho::State::Running(world) => world,
+            moho::State::Quit(_) => panic!("the game quit"),
+        };
+        let assets = assets
+            .next(&world, &fixed::State::default(), &mut manager)
+            .unwrap();
+        Game {
+            world,
+            assets,
+            manager,
+            keyboard,
+        }
+    }
+
+    fn show(&self) -> Recorder {
+        let mut recorder = Recorder::default();
+        self.assets.show(&mut recorder).unwrap();
+        recorder
+    }
+
+    /// Picks co-op from the main menu.
+    fn start_coop(self) -> Self {
+        self.press(&[Keycode::Down]).press(&[Keycode::Return])
+    }
+}
+
+//a floor to stand on with a gem right where the player starts
+const GEM_AT_SPAWN: &str = "
+obstacles:
+  - count: {x: 19, y: 1}
+    bottom_left: {x: 0, y: 0}
+goal: {x: 16, y: 1}
+gems:
+  - {x: 5, y: 1}
+coins: []
+cats: []
+spikes: []
+";
+
+#[test]
+fn menu_shows_every_option() {
+    let game = Game::load("menu_shows_every_option");
+    let texts = game.show().texts().join("|");
+    for option in &["New Game", "Co-op", "Level Select", "High Scores"] {
+        assert!(texts.contains(option), "{} missing from {}", option, texts);
+    }
+}
+
+#[test]
+fn coop_draws_both_players() {
+    let game = Game::load("coop_draws_both_players").start_coop();
+    match game.world.screen {
+        Screen::GamePlay(ref gp, _) => assert!(gp.is_playing()),
+        _ => panic!("co-op did not start a level"),
+    }
+
+    let recorder = game.show();
+    for &kind in &[asset::Texture::Husky, asset::Texture::Duck] {
+        let draws = recorder.draws_of(&Id::Texture(kind));
+        assert_eq!(draws.len(), 1, "{:?} drawn {} times", kind, draws.len());
+    }
+}
+
+#[test]
+fn escape_pauses_the_level() {
+    let game = Game::load("escape_pauses_the_level")
+        .start_coop()
+        .press(&[]);
+    assert!(!game.show().texts().contains(&"PAUSED"));
+
+    let game = game.press(&[Keycode::Escape]);
+    let paused = game.show();
+    assert!(paused.texts().contains(&"PAUSED"));
+
+    //neither time nor the players move while paused
+    let game = game
+        .press(&[Keycode::Right, Keycode::D])
+        .press(&[Keycode::Space, Keycode::W])
+        .press(&[]);
+    assert_eq!(game.show().calls, paused.calls);
+}
+
+#[test]
+fn collecting_a_gem_scores() {
+    let game = Game::load("collecting_a_gem_scores").play(
+        "gem_at_spawn",
+        GEM_AT_SPAWN,
+        Team::Solo(Kind::Husky),
+    );
+    assert!(game.show().texts().contains(&"Score: 00000"));
+
+    let texts = game.press(&[]).show().texts().join("|");
+    assert!(texts.contains("Score: 00050"), "no gem score in {}", texts);
+}
+
+#[test]
+fn ghost_is_drawn_see_through() {
+    let game = Game::load("ghost_is_drawn_see_through");
+    let frame = Frame {
+        x: 0,
+        y: 0,
+        pose: Pose::Idle,
+        facing: HKey::Left,
+    };
+    ghost::Run::new("ghost_race", Kind::Husky, vec![frame; 10]).save();
+
+    let game = game.play("ghost_race", GEM_AT_SPAWN, Team::Solo(Kind::Husky));
+    let recorder = game.show();
+    let draws = recorder.draws_of(&Id::Texture(asset::Texture::Husky));
+    assert_eq!(
+        draws.len(),
+        2,
+        "the player and the ghost are not both drawn"
+    );
+    let ghosts = draws.iter().filter(|d| match ***d {
+        Call::Draw {
+            alpha,
+            flip: Some(Flip::Horizontal),
+            ..
+        } => alpha < 255,
+        _ => false,
+    });
+    assert_eq!(ghosts.count(), 1, "no see-through ghost facing left");
+}
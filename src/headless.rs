@@ -0,0 +1,266 @@
+//! A renderer and asset manager that never touch SDL: textures are just ids
+//! and every draw call is recorded so tests can check what a scene shows.
+
+use crate::{
+    asset::{self, Alpha, Animation, Sprite},
+    data, Result,
+};
+
+use moho::{
+    animation::TileSheet,
+    font,
+    renderer::{
+        options::{self, Options},
+        ColorRGBA, Destination, Draw, Position, Renderer,
+    },
+    texture::{self, Image},
+};
+
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Color(pub u8, pub u8, pub u8, pub u8);
+
+impl<'a> From<&'a ColorRGBA> for Color {
+    fn from(color: &'a ColorRGBA) -> Self {
+        let ColorRGBA(r, g, b, a) = *color;
+        Color(r, g, b, a)
+    }
+}
+
+/// Screen area in pixels, measured from the top left corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Area {
+    pub left: i32,
+    pub top: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<Destination> for Area {
+    fn from(dst: Destination) -> Self {
+        Area {
+            left: dst.left(),
+            top: dst.top(),
+            width: dst.dims.x,
+            height: dst.dims.y,
+        }
+    }
+}
+
+/// What a texture was loaded from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Id {
+    Texture(asset::Texture),
+    Animation(Animation),
+    Text { text: String, color: Color },
+}
+
+#[derive(Clone, Debug)]
+pub struct Texture {
+    pub id: Id,
+    dims: glm::UVec2,
+    alpha: u8,
+}
+
+impl Texture {
+    fn new(id: Id, dims: glm::UVec2) -> Self {
+        Texture {
+            id,
+            dims,
+            alpha: 255,
+        }
+    }
+}
+
+impl asset::Alpha for Texture {
+    fn set_alpha(&mut self, alpha: u8) {
+        self.alpha = alpha;
+    }
+}
+
+impl texture::Texture for Texture {
+    fn dims(&self) -> glm::UVec2 {
+        self.dims
+    }
+}
+
+impl Draw<Recorder> for Texture {
+    fn draw(&self, options: Options, renderer: &mut Recorder) -> Result<()> {
+        renderer.calls.push(Call::Draw {
+            texture: self.id.clone(),
+            src: options.src.map(Area::from),
+            dst: options.dst.map(Area::from),
+            flip: options.flip,
+            alpha: self.alpha,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Call {
+    Draw {
+        texture: Id,
+        //part of the texture drawn, all of it when none
+        src: Option<Area>,
+        dst: Option<Area>,
+        flip: Option<options::Flip>,
+        //alpha the texture is drawn with, 255 being opaque
+        alpha: u8,
+    },
+    Fill {
+        rects: Vec<Area>,
+        color: Color,
+    },
+    Outline {
+        rects: Vec<Area>,
+        color: Color,
+    },
+}
+
+/// Renderer keeping every call made to it, in order.
+pub struct Recorder {
+    pub calls: Vec<Call>,
+    color: Color,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Recorder {
+            calls: vec![],
+            color: Color(0, 0, 0, 255),
+        }
+    }
+}
+
+impl Recorder {
+    /// Every piece of text drawn, in order.
+    pub fn texts(&self) -> Vec<&str> {
+        self.calls
+            .iter()
+            .filter_map(|c| match *c {
+                Call::Draw {
+                    texture: Id::Text { ref text, .. },
+                    ..
+                } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Draw calls of textures loaded from the given asset.
+    pub fn draws_of(&self, id: &Id) -> Vec<&Call> {
+        self.calls
+            .iter()
+            .filter(|c| match **c {
+                Call::Draw { ref texture, .. } => texture == id,
+                _ => false,
+            })
+            .collect()
+    }
+}
+
+impl Renderer for Recorder {
+    fn set_draw_color(&mut self, color: ColorRGBA) {
+        self.color = Color::from(&color);
+    }
+
+    fn fill_rects(&mut self, rects: &[Destination]) -> Result<()> {
+        self.calls.push(Call::Fill {
+            rects: rects.iter().map(|&r| Area::from(r)).collect(),
+            color: self.color,
+        });
+        Ok(())
+    }
+
+    fn draw_rects(&mut self, rects: &[Destination]) -> Result<()> {
+        self.calls.push(Call::Outline {
+            rects: rects.iter().map(|&r| Area::from(r)).collect(),
+            color: self.color,
+        });
+        Ok(())
+    }
+}
+
+/// Monospaced font where every character is half as wide as it is tall.
+pub struct Font {
+    size: u16,
+}
+
+impl font::Font for Font {
+    type Texture = Texture;
+
+    fn measure(&self, text: &str) -> Result<glm::UVec2> {
+        let size = u32::from(self.size);
+        Ok(glm::uvec2(text.chars().count() as u32 * size / 2, size))
+    }
+
+    fn texturize(&self, text: &str, color: &ColorRGBA) -> Result<Texture> {
+        let id = Id::Text {
+            text: text.to_string(),
+            color: Color::from(color),
+        };
+        Ok(Texture::new(id, self.measure(text)?))
+    }
+}
+
+/// Hands out textures sized after the game data without loading any image.
+pub struct Manager {
+    pub data: Rc<data::Game>,
+}
+
+impl asset::Manager for Manager {
+    type Texture = Texture;
+    type Font = Font;
+
+    fn texture(&mut self, texture: asset::Texture) -> Result<Texture> {
+        let dims = self.data.texture(texture).dims.into();
+        Ok(Texture::new(Id::Texture(texture), dims))
+    }
+
+    fn sheet(&mut self, animation: Animation) -> Result<TileSheet<Texture>> {
+        let (tiles, texture) = self.sheet_texture(animation);
+        Ok(TileSheet::new(tiles, texture))
+    }
+
+    fn image(&mut self, texture: asset::Texture, pos: Position) -> Result<Image<Texture>> {
+        let dst = self.data.texture(texture).dims.at(pos);
+        self.texture(texture).map(|texture| Image { texture, dst })
+    }
+
+    fn sprite(&mut self, animation: Animation, pos: Position) -> Result<Sprite<Texture>> {
+        let dst = self.data.animation(animation).dims.at(pos);
+        self.sheet(animation).map(|sheet| Sprite::new(sheet, dst))
+    }
+
+    fn font(&mut self, _: asset::Font, size: u16) -> Result<Rc<Font>> {
+        Ok(Rc::new(Font { size }))
+    }
+
+    fn translucent_texture(&mut self, texture: asset::Texture, alpha: u8) -> Result<Texture> {
+        let mut texture = self.texture(texture)?;
+        texture.set_alpha(alpha);
+        Ok(texture)
+    }
+
+    fn translucent_sheet(&mut self, animation: Animation, alpha: u8) -> Result<TileSheet<Texture>> {
+        let (tiles, mut texture) = self.sheet_texture(animation);
+        texture.set_alpha(alpha);
+        Ok(TileSheet::new(tiles, texture))
+    }
+}
+
+impl Manager {
+    //tiles of an animation and the texture holding all of them
+    fn sheet_texture(&self, animation: Animation) -> (glm::UVec2, Texture) {
+        let data = self.data.animation(animation);
+        let tiles = glm::UVec2::from(data.tiles);
+        let dims = glm::UVec2::from(data.dims);
+        let texture = Texture::new(
+            Id::Animation(animation),
+            glm::uvec2(dims.x * tiles.x, dims.y * tiles.y),
+        );
+        (tiles, texture)
+    }
+}
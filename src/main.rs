@@ -1,16 +1,66 @@
 mod asset;
 mod data;
 mod game;
+#[cfg(test)]
+mod headless;
 mod level_viewer;
+mod storage;
+mod typing;
 mod utils;
 
+use failure::format_err;
 use moho::engine::{step, Engine};
 use sdl2::image::{INIT_JPG, INIT_PNG};
 
-use std::rc::Rc;
+use std::{env, path::PathBuf, process, rc::Rc};
 
 type Result<T> = std::result::Result<T, failure::Error>;
 
+/// Error message including every cause, for logging and showing to the player.
+fn describe(error: &failure::Error) -> String {
+    error
+        .iter_chain()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+/// Logs an error the game carries on from, prefixed with what it affects.
+fn report(what: &str, error: &failure::Error) {
+    eprintln!("{}: {}", what, describe(error));
+}
+
+/// What the game was started to do.
+enum Mode {
+    Play,
+    //`--l`
+    LevelViewer,
+    //`--replay <file>` plays back the replay in the file
+    Replay(PathBuf),
+}
+
+impl Mode {
+    /// Reads the command line arguments, skipping the program name; the last
+    /// mode given wins.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = args.into_iter().skip(1);
+        let mut mode = Mode::Play;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--l" => mode = Mode::LevelViewer,
+                "--replay" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| format_err!("--replay needs the replay file to play"))?;
+                    mode = Mode::Replay(PathBuf::from(path));
+                }
+                _ => {}
+            }
+        }
+        Ok(mode)
+    }
+}
+
 struct Font<F>(F);
 
 impl<F: moho::font::Font> moho::font::Font for Font<F> {
@@ -46,6 +96,11 @@ impl<'f, FL: moho::font::Loader<'f>> moho::resource::Loader<'f, Font<FL::Font>>
 }
 
 fn main() {
+    let mode = Mode::parse(env::args()).unwrap_or_else(|e| {
+        eprintln!("{}", describe(&e));
+        process::exit(2)
+    });
+
     //Setup SDL
     const WINDOW_WIDTH: u32 = 1280;
     const WINDOW_HEIGHT: u32 = 720;
@@ -73,19 +128,23 @@ fn main() {
         .set_logical_size(WINDOW_WIDTH, WINDOW_HEIGHT)
         .unwrap();
     let _image_ctx = sdl2::image::init(INIT_PNG | INIT_JPG).unwrap();
-    let event_pump = sdl_ctx.event_pump().unwrap();
+    let typing = typing::Typing::default();
+    let event_pump = typing::Pump::new(
+        sdl_ctx.event_pump().unwrap(),
+        typing.clone(),
+        video_ctx.text_input(),
+    );
     let texture_loader = canvas.texture_creator();
 
     //Setup Moho
     let step = step::FixedUpdate::default().rate(30);
     let mut engine = Engine::new(event_pump, canvas, step);
 
-    let level_viewer = std::env::args().any(|a| a == "--l");
-    if level_viewer {
-        level_viewer::run(&mut engine)
-    } else {
-        let font_loader = FontLoader::new(&texture_loader);
-        game::run(&mut engine, &texture_loader, &font_loader)
-    }
-    .unwrap()
+    let replay = match mode {
+        Mode::LevelViewer => return level_viewer::run(&mut engine).unwrap(),
+        Mode::Replay(ref path) => Some(path.as_path()),
+        Mode::Play => None,
+    };
+    let font_loader = FontLoader::new(&texture_loader);
+    game::run(&mut engine, &texture_loader, &font_loader, typing, replay).unwrap()
 }
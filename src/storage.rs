@@ -0,0 +1,99 @@
+use crate::Result;
+
+use failure::{format_err, ResultExt};
+
+use std::{
+    env,
+    ffi::OsString,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+
+//overrides where player data is kept, mostly for testing and portable installs
+const DIR_VAR: &str = "HUSKY_LOVES_DUCKY_DATA";
+const APP_DIR: &str = "husky_loves_ducky";
+
+#[cfg(test)]
+thread_local! {
+    //tests run side by side on their own threads, each with its own data
+    static TEST_DIR: std::cell::RefCell<Option<PathBuf>> = Default::default();
+}
+
+/// Keeps the data of the test running on this thread in `dir`.
+#[cfg(test)]
+pub fn use_dir(dir: PathBuf) {
+    TEST_DIR.with(|d| *d.borrow_mut() = Some(dir));
+}
+
+/// Directory the player's data is kept in: `$HUSKY_LOVES_DUCKY_DATA` if set,
+/// otherwise `husky_loves_ducky` under `$XDG_DATA_HOME` or `$HOME/.local/share`.
+pub fn dir() -> Result<PathBuf> {
+    #[cfg(test)]
+    {
+        if let Some(dir) = TEST_DIR.with(|d| d.borrow().clone()) {
+            return Ok(dir);
+        }
+    }
+    let var = |name: &str| {
+        env::var_os(name)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
+    if let Some(dir) = var(DIR_VAR) {
+        return Ok(dir);
+    }
+    //the XDG spec says relative paths are to be ignored
+    let data_home = var("XDG_DATA_HOME")
+        .filter(|d| d.is_absolute())
+        .or_else(|| var("HOME").map(|h| h.join(".local").join("share")))
+        .ok_or_else(|| format_err!("neither {}, XDG_DATA_HOME nor HOME are set", DIR_VAR))?;
+    Ok(data_home.join(APP_DIR))
+}
+
+/// Path of a file in the data directory. The first time it is asked for, the
+/// file is copied from the one bundled with the game, if there is one.
+pub fn seeded(name: &str, bundled: impl AsRef<Path>) -> Result<PathBuf> {
+    let path = dir()?.join(name);
+    if path.exists() {
+        return Ok(path);
+    }
+    let bundled = bundled.as_ref();
+    let mut source = match File::open(bundled) {
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(path),
+        file => file.context(format!("could not open {}", bundled.display()))?,
+    };
+    write(&path, |file| {
+        io::copy(&mut source, file)?;
+        Ok(())
+    })?;
+    Ok(path)
+}
+
+/// Replaces a file all at once by writing a temporary file next to it and
+/// renaming it over the original, so a crash never leaves half a file behind.
+/// Missing parent directories are created.
+pub fn write(path: &Path, contents: impl FnOnce(&mut File) -> Result<()>) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context(format!("could not create {}", dir.display()))?;
+    }
+    let temp = {
+        let mut temp = OsString::from(path.as_os_str());
+        temp.push(".tmp");
+        PathBuf::from(temp)
+    };
+    let written = (|| -> Result<()> {
+        let mut file = File::create(&temp)?;
+        contents(&mut file)?;
+        file.sync_all()?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    })();
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(e
+            .context(format!("could not write {}", path.display()))
+            .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use moho::input;
+use sdl2::{event::Event, keyboard::TextInputUtil};
+
+use std::{cell::RefCell, mem, rc::Rc, vec};
+
+#[derive(Default)]
+struct Shared {
+    //whether a screen is asking for text
+    wanted: bool,
+    text: String,
+}
+
+/// Text typed since it was last taken, for the screens asking the player to
+/// type something.
+#[derive(Clone, Default)]
+pub struct Typing(Rc<RefCell<Shared>>);
+
+impl Typing {
+    /// Asks for text input to be turned on or off from the next poll onwards.
+    pub fn want(&self, wanted: bool) {
+        self.0.borrow_mut().wanted = wanted;
+    }
+
+    pub fn take(&self) -> String {
+        mem::replace(&mut self.0.borrow_mut().text, String::new())
+    }
+}
+
+/// Event pump that keeps the text typed into it and turns SDL's text input on
+/// only while it is wanted.
+pub struct Pump<E> {
+    events: E,
+    typing: Typing,
+    text_input: TextInputUtil,
+}
+
+impl<E> Pump<E> {
+    pub fn new(events: E, typing: Typing, text_input: TextInputUtil) -> Self {
+        //SDL starts with text input on
+        text_input.stop();
+        Pump {
+            events,
+            typing,
+            text_input,
+        }
+    }
+}
+
+impl<E: input::EventPump> input::EventPump for Pump<E> {
+    type I = vec::IntoIter<Event>;
+
+    fn poll_iter(&mut self) -> Self::I {
+        let mut shared = self.typing.0.borrow_mut();
+        match (shared.wanted, self.text_input.is_active()) {
+            (true, false) => self.text_input.start(),
+            (false, true) => self.text_input.stop(),
+            _ => {}
+        }
+        let events: Vec<_> = self.events.poll_iter().collect();
+        for event in &events {
+            if let Event::TextInput { ref text, .. } = *event {
+                shared.text.push_str(text);
+            }
+        }
+        events.into_iter()
+    }
+}
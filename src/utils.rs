@@ -1,20 +1,42 @@
 use moho::input;
 use sdl2::keyboard::Keycode;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
 pub enum HKey {
     Left,
     Right,
 }
 
+/// Keys a single player moves with.
+#[derive(Clone, Copy, Debug)]
+pub struct Controls {
+    pub left: Keycode,
+    pub right: Keycode,
+    pub jump: Keycode,
+}
+
+impl Controls {
+    pub const ARROWS: Controls = Controls {
+        left: Keycode::Left,
+        right: Keycode::Right,
+        jump: Keycode::Space,
+    };
+
+    pub const WASD: Controls = Controls {
+        left: Keycode::A,
+        right: Keycode::D,
+        jump: Keycode::W,
+    };
+}
+
 pub trait InputStateExt {
-    fn hkey(&self) -> Option<HKey>;
+    fn hkey(&self, controls: &Controls) -> Option<HKey>;
 }
 
 impl InputStateExt for input::State {
-    fn hkey(&self) -> Option<HKey> {
-        let left = self.is_key_down(Keycode::Left);
-        let right = self.is_key_down(Keycode::Right);
+    fn hkey(&self, controls: &Controls) -> Option<HKey> {
+        let left = self.is_key_down(controls.left);
+        let right = self.is_key_down(controls.right);
         match (left, right) {
             (true, false) => Some(HKey::Left),
             (false, true) => Some(HKey::Right),